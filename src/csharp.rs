@@ -1,51 +1,56 @@
-use asr::{Address64, Process};
+use asr::Address64;
 use core::marker::PhantomData;
 
 use bytemuck::AnyBitPattern;
 
+use crate::memory::MemoryReader;
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug, AnyBitPattern)]
-pub struct CSharpList<T: AnyBitPattern> {
+pub struct CSharpList<T> {
     address: Address64,
     phantom_data: PhantomData<T>,
 }
 
 impl<T: AnyBitPattern> CSharpList<T> {
-    /*
     /// Retrieve the number of elements in the current List object
-    pub fn get_count(&self, process: &Process) -> Option<usize> {
+    pub fn get_count<R: MemoryReader>(&self, process: &R) -> Option<usize> {
         process
-            .read_pointer(self.address, PointerSize::Bit64)
-            .and_then(|addr| process.read::<u32>(addr + 0x18))
+            .read::<u32>(self.address + 0x18)
             .map(|val| val as usize)
-            .ok()
     }
-    */
 
-    /// Iterates over all the elements of the current List
-    pub fn iter<'a>(&self, process: &'a Process) -> impl DoubleEndedIterator<Item = T> + 'a {
-        let raw_data = process.read::<[u8; 0x1C]>(self.address).ok();
+    /// Reads the address of the backing array and the number of elements in the List
+    fn header<R: MemoryReader>(&self, process: &R) -> Option<(Address64, usize)> {
+        let raw_data = process.read::<[u8; 0x1C]>(self.address)?;
 
-        let data_pointer =
-            raw_data.map(|data| unsafe { *(data.as_ptr().byte_add(0x10) as *const Address64) })
-            .filter(|val| !val.is_null());
+        let data_pointer = bytemuck::pod_read_unaligned::<Address64>(&raw_data[0x10..0x18]);
+        let count = bytemuck::pod_read_unaligned::<u32>(&raw_data[0x18..0x1C]) as usize;
+
+        match data_pointer.is_null() || count == 0 {
+            true => None,
+            false => Some((data_pointer, count)),
+        }
+    }
 
-        let count = raw_data
-            .map(|data| unsafe { *(data.as_ptr().byte_add(0x18) as *const u32) })
-            .filter(|&val| val != 0)
-            .map(|val| val as usize);
+    /// Iterates over all the elements of the current List.
+    ///
+    /// This is meant for lists of reference types (eg. `List<SomeClass>`), where the
+    /// backing array holds pointers to the actual objects.
+    pub fn iter<'a, R: MemoryReader>(
+        &self,
+        process: &'a R,
+    ) -> impl DoubleEndedIterator<Item = T> + 'a {
+        let header = self.header(process);
 
-        let elements = match (data_pointer, count) {
-            (Some(data_pointer), Some(count)) => process
-                .read_vec::<Address64>(data_pointer + 0x20, count)
-                .ok(),
-            _ => None,
-        };
+        let elements = header.and_then(|(data_pointer, count)| {
+            process.read_vec::<Address64>(data_pointer + 0x20, count)
+        });
 
-        (0..count.unwrap_or_default()).filter_map(move |val| {
+        (0..header.map(|(_, count)| count).unwrap_or_default()).filter_map(move |val| {
             elements
                 .as_ref()
-                .and_then(|element| process.read(element[val]).ok())
+                .and_then(|element| process.read(element[val]))
         })
     }
 
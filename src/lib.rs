@@ -9,30 +9,98 @@
 )]
 
 extern crate alloc;
-use alloc::vec::Vec;
+use alloc::{collections::VecDeque, format, string::String, vec, vec::Vec};
 use asr::{
-    future::{next_tick, retry},
+    future::next_tick,
     game_engine::unity::get_scene_name,
     settings::{gui::Title, Gui},
+    string::ArrayCString,
     time::Duration,
+    time_util::Instant,
     timer::{self, TimerState},
-    watcher::Watcher,
-    Process,
+    watcher::{Pair, Watcher},
+    Address64, Process,
 };
 use bytemuck::Zeroable;
+use core::{
+    cell::{Cell, RefCell},
+    fmt::{self, Write},
+};
 use csharp::CSharpList;
+use memory::MemoryReader;
 use mono::{Image, Module, UnityPointer};
 use scene_manager::SceneManager;
+use transform::Transform;
 
 mod csharp;
+mod memory;
 mod mono;
 mod scene_manager;
+mod transform;
 
 asr::panic_handler!();
 asr::async_main!(stable);
 
-const PROCESS_NAMES: &[&str] = &["Little Kitty, Big City.exe"];
-const USE_LINUX_WORKAROUND: bool = true;
+/// Process names the autosplitter can hook to, along with whether the Linux
+/// workaround should be used for that name.
+///
+/// On Linux, the process name (`comm`) is limited to 15 characters, so games running
+/// through Wine/Proton show up with a truncated name. When the workaround is enabled,
+/// the autosplitter also tries to attach to the first 15 characters of the name.
+/// This should be enabled only for names that actually need it, as a truncated name
+/// can also match unrelated processes.
+const PROCESS_NAMES: &[(&str, bool)] = &[("Little Kitty, Big City.exe", true)];
+
+/// Maximum length of a process name on Linux
+const LINUX_PROCESS_NAME_LEN: usize = 15;
+
+/// Size of the buffer used to read scene paths. Paths that don't fit are discarded,
+/// as a truncated path could yield the wrong scene name.
+const SCENE_PATH_LEN: usize = 256;
+
+/// ID of the cat-chievement awarded when the cat gets back home
+const GOT_HOME_ACHIEVEMENT: u32 = 39;
+
+/// Name of the scene the game shows while loading the world
+const LOADING_SCENE: &[u8] = b"Loading";
+
+/// Name of the scene of the main menu
+const MAIN_MENU_SCENE: &[u8] = b"MainMenu_LKBC";
+
+/// A scene name, along with the setting enabling the behavior tied to it
+type SceneSetting = (&'static [u8], fn(&Settings) -> bool);
+
+/// Scenes with a configurable load removal behavior, along with the setting
+/// deciding whether game time is paused while they are the active scene. A disabled
+/// entry doesn't keep the other loads from pausing game time, unless
+/// `load_scenes_override` is enabled.
+const LOAD_SCENES: &[SceneSetting] = &[
+    (LOADING_SCENE, |settings| settings.pause_in_loading_scene),
+    (MAIN_MENU_SCENE, |settings| settings.pause_in_main_menu),
+];
+
+/// Scenes that can trigger a split when they become the active scene, or when
+/// they stop being so if `split_on_scene_exit` is enabled, along with the setting
+/// enabling the split. Each scene splits at most once per run.
+const SPLIT_SCENES: &[SceneSetting] = &[(b"Level_X", |settings| settings.split_scene_level)];
+
+/// Non-gameplay scenes in which the run can never be started or split, as a
+/// safety net against spurious triggers. The outro must not be listed here, as
+/// the game end is detected while it's the active scene.
+const IGNORED_SCENES: &[&[u8]] = &[MAIN_MENU_SCENE];
+
+/// Distance from the spawn point within which the cat is considered at the spawn point
+const SPAWN_RADIUS: f32 = 2.0;
+
+/// Minimum distance covered by the cat in a single update for it to count as respawned,
+/// rather than just walking back to the spawn point
+const SPAWN_SNAP_DISTANCE: f32 = 10.0;
+
+/// Minimum game time since the start before the spawn point can trigger a reset
+const SPAWN_RESET_GRACE: Duration = Duration::seconds(10);
+
+/// Maximum number of ticks waited between two attempts to hook to the game
+const MAX_ATTACH_BACKOFF_TICKS: u32 = 64;
 
 #[global_allocator]
 static ALLOC: dlmalloc::GlobalDlmalloc = dlmalloc::GlobalDlmalloc;
@@ -45,17 +113,11 @@ async fn main() {
         // First thing to do in the autosplitter logic is to hook to the target process.
         // This needs to stay inside the loop as the autosplitter must re-try to hook
         // to the target process once it is exited.
-        let (process, process_name) = retry(|| {
-            PROCESS_NAMES.iter().find_map(|&name| {
-                let mut proc = Process::attach(name);
-                if proc.is_none() && USE_LINUX_WORKAROUND && name.len() > 15 {
-                    proc = Process::attach(&name[0..15])
-                }
+        let process = attach_process().await;
 
-                Some((proc?, name))
-            })
-        })
-        .await;
+        if settings.log_splits_template {
+            log_splits_template(&settings);
+        }
 
         process
             .until_closes(async {
@@ -66,13 +128,14 @@ async fn main() {
                 // loading the settings. In the majority of cases, however,
                 // this is not necessary.
                 let mut watchers = Watchers::default();
+                let mut run_state = RunState::default();
+                let mut variables = Variables::default();
 
                 // Perform memory scanning to look for the addresses we need.
                 // Depending on the game and the logic, we can either define fixed
                 // memory offsets, or perform more advanced stuff (eg. sigscanning).
-                // The name of the executable is passed here in order to easily allow
-                // to query for the process' main module.
-                let addresses = Memory::init(&process, process_name).await;
+                let mut addresses = Memory::init(&process).await;
+                let mut offsets_logged = false;
 
                 loop {
                     // Splitting logic. Adapted from OG LiveSplit:
@@ -82,9 +145,26 @@ async fn main() {
                     // 3. If reset does not return true, then the split action will be run.
                     // 4. If the timer is currently not running (and not paused), then the start action will be run.
                     settings.update();
-                    update_loop(&process, &addresses, &mut watchers);
+                    addresses.update_scene_manager(&process);
+                    addresses.update_mono(&process);
+
+                    if settings.log_offsets && !offsets_logged {
+                        if let Some(mono) = &addresses.mono {
+                            mono.log_offsets(&process);
+                            offsets_logged = true;
+                        }
+                    }
+                    watchers.update(Readings::read(&process, &addresses, &settings));
+                    update_run_state(&watchers, &settings, &mut run_state);
+                    update_variables(&mut variables, &watchers, &settings, &run_state);
 
-                    if [TimerState::Running, TimerState::Paused].contains(&timer::state()) {
+                    // The variables above are kept up to date even if the autosplitter
+                    // is not allowed to control the timer
+                    let timer_state = timer_state(&settings, &run_state);
+                    if settings.autosplitter_enabled
+                        && !settings.dry_run
+                        && [TimerState::Running, TimerState::Paused].contains(&timer_state)
+                    {
                         if let Some(val) = is_loading(&watchers, &settings) {
                             match val {
                                 true => timer::pause_game_time(),
@@ -95,27 +175,48 @@ async fn main() {
                         if let Some(game_time) = game_time(&watchers, &settings, &addresses) {
                             timer::set_game_time(game_time);
                         }
+                    }
 
-                        match reset(&watchers, &settings) {
-                            true => timer::reset(),
-                            false => {
-                                if split(&watchers, &settings) {
-                                    timer::split();
+                    match next_action(&watchers, &settings, &mut run_state, timer_state) {
+                        Some(Action::Reset) => match settings.dry_run {
+                            true => {
+                                asr::print_message("Dry run: reset");
+                                run_state.dry_run_started = false;
+                            }
+                            false => timer::reset(),
+                        },
+                        Some(Action::Split(event)) => match settings.dry_run {
+                            true => {
+                                asr::print_limited::<128>(&format_args!("Dry run: split ({event})"))
+                            }
+                            false => timer::split(),
+                        },
+                        Some(Action::Start) => {
+                            match settings.dry_run {
+                                true => {
+                                    asr::print_message("Dry run: start");
+                                    run_state.dry_run_started = true;
+                                }
+                                false => {
+                                    timer::start();
+                                    timer::pause_game_time();
                                 }
                             }
-                        }
-                    }
 
-                    if timer::state().eq(&TimerState::NotRunning) && start(&watchers, &settings) {
-                        timer::start();
-                        timer::pause_game_time();
+                            if settings.log_quest_settings {
+                                log_quest_settings(&settings);
+                            }
 
-                        if let Some(val) = is_loading(&watchers, &settings) {
-                            match val {
-                                true => timer::pause_game_time(),
-                                false => timer::resume_game_time(),
-                            };
+                            if let Some(val) =
+                                is_loading(&watchers, &settings).filter(|_| !settings.dry_run)
+                            {
+                                match val {
+                                    true => timer::pause_game_time(),
+                                    false => timer::resume_game_time(),
+                                };
+                            }
                         }
+                        None => {}
                     }
 
                     next_tick().await;
@@ -125,21 +226,162 @@ async fn main() {
     }
 }
 
+/// Action taken on the timer in an update
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Action {
+    Start,
+    Split(SplitEvent),
+    Reset,
+}
+
+/// Decides whether the run gets started, split or reset in the current update,
+/// given the state of the timer, and updates the run state accordingly. At most
+/// one action is taken per update. The timer itself is left untouched.
+fn next_action(
+    watchers: &Watchers,
+    settings: &Settings,
+    state: &mut RunState,
+    timer_state: TimerState,
+) -> Option<Action> {
+    if !settings.autosplitter_enabled {
+        return None;
+    }
+
+    match timer_state {
+        TimerState::Running | TimerState::Paused => {
+            if reset(watchers, settings, state) {
+                // The conditions of the reset might also satisfy the start
+                // ones, so the run doesn't get restarted right away
+                state.start_cooldown_ticks = RESET_START_COOLDOWN_TICKS;
+                return Some(Action::Reset);
+            }
+
+            let event = split(watchers, settings, state)?;
+            state.last_split_game_time = state.game_time;
+            state.splits_fired += 1;
+            Some(Action::Split(event))
+        }
+        TimerState::NotRunning if start(watchers, settings, state) => {
+            state.reset();
+            state.start_armed = false;
+            state.spawn_position = watchers.cat_position.pair.map(|val| val.current);
+            Some(Action::Start)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the state of the timer. In dry run mode, the timer is never touched,
+/// so the state of the simulated run is returned instead.
+fn timer_state(settings: &Settings, state: &RunState) -> TimerState {
+    match settings.dry_run {
+        true if state.dry_run_started => TimerState::Running,
+        true => TimerState::NotRunning,
+        false => timer::state(),
+    }
+}
+
+/// Hooks to the game, waiting for an increasing number of ticks (up to a cap)
+/// between each attempt, so the game isn't queried continuously while it's closed.
+async fn attach_process() -> Process {
+    let mut backoff_ticks = 1;
+
+    loop {
+        let process = PROCESS_NAMES.iter().find_map(|&(name, linux_workaround)| {
+            let mut proc = Process::attach(name);
+            if proc.is_none() && linux_workaround && name.len() > LINUX_PROCESS_NAME_LEN {
+                proc = Process::attach(&name[0..LINUX_PROCESS_NAME_LEN])
+            }
+
+            proc
+        });
+
+        if let Some(process) = process {
+            return process;
+        }
+
+        for _ in 0..backoff_ticks {
+            next_tick().await;
+        }
+        backoff_ticks = (backoff_ticks * 2).min(MAX_ATTACH_BACKOFF_TICKS);
+    }
+}
+
 #[derive(Gui)]
 struct Settings {
     /// General settings
     general: Title,
     #[default = true]
+    /// Let the autosplitter control the timer. When disabled, the variables keep being
+    /// updated, but the timer is never started, split, reset or paused.
+    autosplitter_enabled: bool,
+    #[default = true]
     /// Enable auto start
     start: bool,
+    #[default = true]
+    /// Only allow the auto start after a fresh world load (loading scene followed by the level)
+    start_requires_world_load: bool,
+    #[default = false]
+    /// Reset when the cat respawns where the run started (runs started by the autosplitter only)
+    reset_on_spawn: bool,
+    #[default = false]
+    /// Require the start flag to be set for two consecutive ticks (ignores single-frame flickers)
+    debounce_start: bool,
+    /// Load removal
+    load_removal: Title,
+    /// Pause game time in the loading scene
+    #[default = true]
+    pause_in_loading_scene: bool,
+    /// Pause game time in the main menu
+    #[default = true]
+    pause_in_main_menu: bool,
+    /// Keep game time running in the scenes above when they are not paused, even if the
+    /// game is loading a save or the cat is teleporting
+    #[default = false]
+    load_scenes_override: bool,
+    /// Pause game time while teleporting through a portal
+    #[default = true]
+    pause_during_teleport: bool,
     /// Splitting settings
     split: Title,
     /// Split after eating fish
     #[default = true]
     eat_fish: bool,
+    /// Order in which splits triggered at the same time are fired (the game end always comes last)
+    split_priority: SplitPriority,
+    /// Split only on the first completed quest or cat-chievement
+    #[default = false]
+    split_only_first_quest: bool,
+    /// Minimum game time since the start before any split can trigger
+    min_run_seconds: MinRunDuration,
+    /// Merge quests and cat-chievements completed shortly after a quest split into that split
+    quest_merge_window: QuestMergeWindow,
     /// Split on game end
     #[default = true]
     got_home: bool,
+    /// Signal used for the game end split
+    game_end_source: GameEndSource,
+    /// Require the game end flag to be set for two consecutive ticks (ignores single-frame flickers)
+    #[default = false]
+    debounce_end: bool,
+    /// Allow the game end split to trigger again in the same run (eg. if the outro is replayed)
+    #[default = false]
+    allow_end_retrigger: bool,
+    /// Split once every quest and cat-chievement is complete (100%)
+    #[default = false]
+    split_on_100_percent: bool,
+    /// Split once every main quest is complete and a fish has been eaten, in any order
+    #[default = false]
+    split_on_quests_and_fish: bool,
+    /// Split when entering the city level scene
+    #[default = false]
+    split_scene_level: bool,
+    /// Split on leaving the city level scene instead of entering it
+    #[default = false]
+    split_on_scene_exit: bool,
+    /// Split on every portal use (teleport)
+    #[default = false]
+    split_on_teleport: bool,
     /// Quest list
     quests: Title,
     /// Find the crow
@@ -196,6 +438,12 @@ struct Settings {
     /// Find Chameleon: The Return of Chaml
     #[default = true]
     find_chameleon_8: bool,
+    /// Find all the chameleons (single split, independent of the settings above)
+    #[default = false]
+    split_on_all_chameleons: bool,
+    /// End the run when all the chameleons are found, instead of on the outro (chameleon category)
+    #[default = false]
+    end_on_last_chameleon: bool,
     /// Steal the gardener's lunch
     #[default = true]
     steal_lunch: bool,
@@ -309,310 +557,1381 @@ struct Settings {
     /// Dumpster Diving (dive trash)
     #[default = false]
     dumpster_diving: bool,
+    /// Debug settings
+    debug: Title,
+    /// Log the reason of every split
+    #[default = false]
+    log_splits: bool,
+    /// Log the quests and cat-chievements enabled for splitting at the start of the run
+    #[default = false]
+    log_quest_settings: bool,
+    /// Log the list of the splits enabled in the settings once the game is hooked,
+    /// to help setting up the splits in LiveSplit
+    #[default = false]
+    log_splits_template: bool,
+    /// Show the state of the trash can shake (used by the auto start) as variables
+    #[default = false]
+    show_start_trigger_state: bool,
+    /// Dry run: log the starts, splits and resets instead of sending them to the timer
+    #[default = false]
+    dry_run: bool,
+    /// Log the game time at which each tracked quest got completed at the end of the run
+    #[default = false]
+    log_quest_times: bool,
+    /// Log every time the game time gets paused or resumed, along with the scene and the cause
+    #[default = false]
+    log_pauses: bool,
+    /// Log the resolved achievement offsets and quest list addresses once Mono is loaded
+    #[default = false]
+    log_offsets: bool,
+}
+
+#[derive(Gui, Copy, Clone, PartialEq, Eq)]
+enum GameEndSource {
+    /// Outro flag
+    #[default]
+    OutroFlag,
+    /// Little Kitty, Big City cat-chievement (got home)
+    GotHomeAchievement,
+}
+
+#[derive(Gui, Copy, Clone, PartialEq, Eq)]
+enum SplitPriority {
+    /// Quests, cat-chievements, eating fish, other events
+    #[default]
+    QuestsFirst,
+    /// Eating fish, quests, cat-chievements, other events
+    FishFirst,
+}
+
+#[derive(Gui, Copy, Clone, PartialEq, Eq)]
+enum MinRunDuration {
+    /// No minimum
+    #[default]
+    Off,
+    /// 5 seconds
+    Seconds5,
+    /// 10 seconds
+    Seconds10,
+    /// 30 seconds
+    Seconds30,
+}
+
+impl MinRunDuration {
+    const fn duration(self) -> Duration {
+        match self {
+            Self::Off => Duration::ZERO,
+            Self::Seconds5 => Duration::seconds(5),
+            Self::Seconds10 => Duration::seconds(10),
+            Self::Seconds30 => Duration::seconds(30),
+        }
+    }
+}
+
+#[derive(Gui, Copy, Clone, PartialEq, Eq)]
+enum QuestMergeWindow {
+    /// Off
+    #[default]
+    Off,
+    /// 1 second
+    Seconds1,
+    /// 3 seconds
+    Seconds3,
+    /// 5 seconds
+    Seconds5,
+}
+
+impl QuestMergeWindow {
+    /// Returns the window of game time, or `None` if merging is disabled
+    const fn duration(self) -> Option<Duration> {
+        match self {
+            Self::Off => None,
+            Self::Seconds1 => Some(Duration::seconds(1)),
+            Self::Seconds3 => Some(Duration::seconds(3)),
+            Self::Seconds5 => Some(Duration::seconds(5)),
+        }
+    }
+}
+
+/// The events that can trigger a split
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum SplitEvent {
+    /// Completion of a quest or cat-chievement
+    Quest(QuestList, u32),
+    /// Any other event, along with its description
+    Other(&'static str),
+}
+
+impl fmt::Display for SplitEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Quest(list, quest_id) => write!(
+                f,
+                "{} {} ({})",
+                match list {
+                    QuestList::Main => "quest",
+                    QuestList::Secondary => "cat-chievement",
+                },
+                quest_id,
+                list.name(quest_id).unwrap_or("unknown"),
+            ),
+            Self::Other(description) => f.write_str(description),
+        }
+    }
+}
+
+/// Number of ticks during which an event can't trigger another split after firing
+const EVENT_COOLDOWN_TICKS: u32 = 30;
+
+/// Number of ticks during which the run can't be started after the autosplitter resets it
+const RESET_START_COOLDOWN_TICKS: u32 = 10;
+
+/// Prevents an event from triggering multiple splits in a row, in case the
+/// flag it depends on latches or flickers for a few ticks
+#[derive(Copy, Clone, Default)]
+struct Cooldown {
+    remaining_ticks: u32,
+}
+
+impl Cooldown {
+    /// Returns whether the event can trigger a split, starting the cooldown if it does
+    fn check(&mut self, triggered: bool) -> bool {
+        if self.remaining_ticks > 0 {
+            self.remaining_ticks -= 1;
+            return false;
+        }
+
+        if triggered {
+            self.remaining_ticks = EVENT_COOLDOWN_TICKS;
+        }
+        triggered
+    }
+}
+
+/// State of the current run, reset whenever the timer is not running
+#[derive(Default)]
+struct RunState {
+    /// Splits triggered at the same time as another split, fired one per tick
+    pending_splits: VecDeque<SplitEvent>,
+    /// Whether a quest or cat-chievement split has already been triggered
+    quest_split_done: bool,
+    /// Game time at the moment of the last quest or cat-chievement split
+    last_quest_split_game_time: Option<Duration>,
+    /// Whether the 100% split has already been triggered
+    hundred_percent_done: bool,
+    /// Whether a fish has been eaten in the current run
+    fish_eaten: bool,
+    /// Whether the split on all the main quests and a fish eaten has already been triggered
+    quests_and_fish_done: bool,
+    /// Whether the game end split has already been triggered
+    game_end_done: bool,
+    /// Cooldown of the split on portal use, so a single teleport splits once
+    teleport_cooldown: Cooldown,
+    /// Bitset of the indexes in `SPLIT_SCENES` of the scenes that already triggered a split
+    scenes_split: u32,
+    /// Last tracked quest or cat-chievement completed in the current run
+    last_completed_quest: Option<(QuestList, u32)>,
+    /// Game time at which each tracked quest and cat-chievement got completed, in order
+    quest_times: Vec<(QuestList, u32, Duration)>,
+    /// Path of the scene that was active before the current one
+    last_scene: Option<ArrayCString<SCENE_PATH_LEN>>,
+    /// Whether a run has been started in dry run mode
+    dry_run_started: bool,
+    /// Whether the auto start is armed, ie. the world has been freshly loaded
+    start_armed: bool,
+    /// Ticks left before the auto start can trigger again, after a reset issued by the autosplitter
+    start_cooldown_ticks: u32,
+    /// Position of the cat when the run got started by the autosplitter
+    spawn_position: Option<[f32; 3]>,
+    /// Game time elapsed in the current run, as measured by the autosplitter
+    game_time: Duration,
+    /// Time spent loading in the current run
+    load_time: Duration,
+    /// Number of splits fired in the current run
+    splits_fired: u32,
+    /// Game time at the moment of the last split
+    last_split_game_time: Duration,
+    /// Moment of the last update of the run state
+    last_tick: Option<Instant>,
+    /// Whether the game time was paused at the last update, as logged by `log_pauses`
+    game_time_paused: Option<bool>,
+}
+
+impl RunState {
+    /// Clears everything tracked for the current run. Only the state that carries
+    /// over from one run to the next, like the arming of the auto start, is kept.
+    fn reset(&mut self) {
+        *self = Self {
+            last_scene: self.last_scene.take(),
+            dry_run_started: self.dry_run_started,
+            start_armed: self.start_armed,
+            start_cooldown_ticks: self.start_cooldown_ticks,
+            last_tick: self.last_tick,
+            ..Self::default()
+        };
+    }
+
+    /// Lets the cooldowns of the event splits run for one tick without any trigger
+    fn advance_cooldowns(&mut self) {
+        self.teleport_cooldown.check(false);
+    }
 }
 
 struct Memory {
+    scene_manager: SceneManager,
+    mono: Option<MonoMemory>,
+}
+
+/// Everything that depends on the Mono backend. This is loaded separately from the
+/// scene manager, so scene-based features keep working even if Mono is not available.
+struct MonoMemory {
     mono_module: Module,
     mono_image: Image,
-    scene_manager: crate::scene_manager::SceneManager,
-
-    trashcan_allow_shake: UnityPointer<3>,
-    is_loading_save: UnityPointer<2>,
-    is_teleporting: UnityPointer<2>,
-    is_outro: UnityPointer<2>,
-    quest_list: UnityPointer<1>,
-    quest_secondary_list: UnityPointer<1>,
 
-    post_eat: UnityPointer<2>,
-    offset_achievement_id: usize,
-    offset_achievement_completed: usize,
+    cat_player: CatPlayer,
+    // The pointers below are `None` if any of their fields is missing on the current
+    // game version, in which case the features using them are disabled
+    trashcan: Option<UnityPointer<2>>,
+    trashcan_allow_shake: Option<UnityPointer<3>>,
+    is_loading_save: Option<UnityPointer<2>>,
+    is_outro: Option<UnityPointer<2>>,
+    quest_list: UnityPointer<2>,
+    quest_secondary_list: UnityPointer<2>,
+    achievement_layout: AchievementLayout,
+    /// Whether the achievement fields fit inside the memory read for each achievement
+    quests_available: bool,
+    /// Whether a quest list exceeding `MAX_QUEST_LIST_LEN` has already been logged
+    quest_list_overflow_logged: Cell<bool>,
 }
 
 impl Memory {
-    async fn init(game: &Process, _process_name: &str) -> Self {
+    async fn init(game: &Process) -> Self {
         asr::print_message("Autosplitter loading...");
 
-        asr::print_message("  => Loading Mono module...");
-        let mono_module = Module::wait_attach_auto_detect(game).await;
-        asr::print_message("    => Found Mono module");
-
-        asr::print_message("  => Loading Assembly-CSharp.dll...");
-        let mono_image = mono_module.wait_get_default_image(game).await;
-        asr::print_message("    => Found Assembly-CSharp.dll");
-
         asr::print_message("  => Loading Scene Manager...");
         let scene_manager = SceneManager::wait_attach(game).await;
-        asr::print_message("    => Found Scene Manager");
-
-        asr::print_message("  => Setting up memory watchers...");
-        let trashcan_allow_shake = UnityPointer::new(
-            "CatPlayer",
-            0,
-            &["_instance", "trashDive_TrashCan", "allowPlayerShake"],
-        );
-        let is_loading_save =
-            UnityPointer::new("CatSaveSystemManager", 0, &["_instance", "_isLoading"]);
-        let is_teleporting = UnityPointer::new("CatPlayer", 0, &["_instance", "isTeleporting"]);
-        let is_outro = UnityPointer::new("CatGameManager", 0, &["_instance", "isInOutro"]);
-        let quest_list = UnityPointer::new("Journal", 0, &["achievementMaster"]);
-        let quest_secondary_list = UnityPointer::new("Journal", 0, &["achievementSecondary"]);
-        let post_eat = UnityPointer::new("CatPlayer", 0, &["_instance", "isPostEating"]);
-
-        let achievement_class = mono_image
-            .wait_get_class(game, &mono_module, "Achievement")
-            .await;
+        asr::print_limited::<128>(&format_args!(
+            "    => Found Scene Manager in {}",
+            scene_manager.module_name()
+        ));
 
-        let offset_achievement_id = achievement_class
-            .wait_get_field_offset(game, &mono_module, "id")
-            .await as usize;
-        let offset_achievement_completed = achievement_class
-            .wait_get_field_offset(game, &mono_module, "_completed")
-            .await as usize;
-        asr::print_message("    => Done!");
+        asr::print_message("  => Loading Mono...");
+        let mono = MonoMemory::init(game);
+        match &mono {
+            Some(mono) if mono.quests_available && mono.pointers_available() => {
+                asr::print_message("    => Full feature set active")
+            }
+            Some(mono) if mono.quests_available => {
+                asr::print_message("    => Degraded mode: the features listed above are disabled")
+            }
+            Some(_) => asr::print_message("    => Degraded mode: quest splits disabled"),
+            _ => asr::print_message(
+                "    => Mono not available: scene-based features only (start, load removal)",
+            ),
+        }
 
         asr::print_limited::<24>(&" => Autosplitter ready!");
 
-        Self {
+        let memory = Self {
+            scene_manager,
+            mono,
+        };
+        memory.set_arch_variable();
+        memory.set_status_variable();
+        memory
+    }
+
+    /// Publishes whether all the features are available, so users can tell a game
+    /// version not fully supported apart from a configuration problem
+    fn set_status_variable(&self) {
+        timer::set_variable(
+            "Status",
+            match &self.mono {
+                Some(mono) if mono.quests_available && mono.pointers_available() => "OK",
+                Some(mono) if mono.quests_available => "Degraded: some features off",
+                Some(_) => "Degraded: quest splits off",
+                None => "Scene-based features only",
+            },
+        );
+    }
+
+    /// Publishes the architecture of the game and the detected Mono backend, for bug reports
+    fn set_arch_variable(&self) {
+        timer::set_variable(
+            "Arch",
+            &format!(
+                "{}-bit {}",
+                self.scene_manager.pointer_size() as u8 * 8,
+                match self.mono.as_ref().map(|mono| mono.mono_module.version()) {
+                    Some(mono::Version::V1) => "Mono V1",
+                    Some(mono::Version::V2) => "Mono V2",
+                    Some(mono::Version::V3) => "Mono V3",
+                    None => "(no Mono)",
+                }
+            ),
+        );
+    }
+
+    /// Attaches to the scene manager again if the active scene can't be read anymore.
+    fn update_scene_manager(&mut self, game: &Process) {
+        if self.scene_manager.refresh(game) {
+            asr::print_message("  => Scene Manager re-attached after repeated read failures");
+        }
+    }
+
+    /// Retries loading the Mono-dependent features, if they are not available yet.
+    fn update_mono(&mut self, game: &Process) {
+        if self.mono.is_none() {
+            self.mono = MonoMemory::init(game);
+
+            if let Some(mono) = &self.mono {
+                asr::print_message(match mono.quests_available {
+                    true if mono.pointers_available() => {
+                        "  => Mono loaded: full feature set active"
+                    }
+                    true => "  => Mono loaded: degraded mode, some features disabled",
+                    false => "  => Mono loaded: degraded mode, quest splits disabled",
+                });
+                self.set_arch_variable();
+                self.set_status_variable();
+            }
+        }
+    }
+}
+
+impl MonoMemory {
+    fn init(game: &Process) -> Option<Self> {
+        let mono_module = Module::attach_auto_detect(game)?;
+        let mono_image = mono_module.get_default_image(game)?;
+
+        let cat_player = CatPlayer::init(game, &mono_module, &mono_image)?;
+
+        // The trash can pointer is a prefix of the flag's path, so it's checked along with it
+        let trashcan_allow_shake = Self::checked_pointer(
+            game,
+            &mono_module,
+            &mono_image,
+            UnityPointer::new(
+                "CatPlayer",
+                0,
+                &["_instance", "trashDive_TrashCan", "allowPlayerShake"],
+            ),
+            "start on leaving the trash can",
+        );
+        let trashcan = trashcan_allow_shake
+            .as_ref()
+            .map(|_| UnityPointer::new("CatPlayer", 0, &["_instance", "trashDive_TrashCan"]));
+        let is_loading_save = Self::checked_pointer(
+            game,
+            &mono_module,
+            &mono_image,
+            UnityPointer::new("CatSaveSystemManager", 0, &["_instance", "_isLoading"]),
+            "loading flag",
+        );
+        let is_outro = Self::checked_pointer(
+            game,
+            &mono_module,
+            &mono_image,
+            UnityPointer::new("CatGameManager", 0, &["_instance", "isInOutro"]),
+            "game end on the outro flag",
+        );
+        // Unlike most other classes, `Journal` holds the quest lists in static fields, with
+        // no `_instance` singleton. In case this changes, we look for the singleton and go
+        // through it if present.
+        let journal_instance = mono_image
+            .get_class(game, &mono_module, "Journal")
+            .and_then(|class| class.get_field_offset(game, &mono_module, "_instance"))
+            .is_some();
+        let journal_pointer = |field: &'static str| match journal_instance {
+            true => UnityPointer::new("Journal", 0, &["_instance", field]),
+            false => UnityPointer::new("Journal", 0, &[field]),
+        };
+        let quest_list = journal_pointer("achievementMaster");
+        let quest_secondary_list = journal_pointer("achievementSecondary");
+        let journal_missing_field = [&quest_list, &quest_secondary_list]
+            .into_iter()
+            .find_map(|list| list.find_missing_field(game, &mono_module, &mono_image));
+        if let Some(name) = journal_missing_field {
+            asr::print_limited::<128>(&format_args!(
+                "    => Quest lists not found: missing {name}"
+            ));
+        }
+        // Quest tracking depends on these, but the rest of the autosplitter doesn't,
+        // so their absence only disables the quest-related features
+        let achievement_class = mono_image.get_class(game, &mono_module, "Achievement");
+        let achievement_offset = |name: &str| {
+            achievement_class
+                .as_ref()?
+                .get_field_offset(game, &mono_module, name)
+                .map(|offset| offset as usize)
+        };
+
+        let completed_field = ACHIEVEMENT_COMPLETED_FIELDS
+            .iter()
+            .find_map(|&name| Some((name, achievement_offset(name)?)));
+        if let Some((name, _)) = completed_field {
+            asr::print_limited::<128>(&format_args!("    => Achievement completion field: {name}"));
+        }
+
+        let quest_offsets = achievement_offset("id")
+            .zip(completed_field.map(|(_, offset)| offset))
+            .filter(|&(id, completed)| {
+                id + size_of::<u32>() <= ACHIEVEMENT_SIZE
+                    && completed + size_of::<bool>() <= ACHIEVEMENT_SIZE
+            });
+        let quests_available = quest_offsets.is_some() && journal_missing_field.is_none();
+        let (id, completed) = quest_offsets.unwrap_or_default();
+        let achievement_layout = AchievementLayout { id, completed };
+
+        if !quests_available {
+            asr::print_message(
+                "  => DEGRADED MODE: quest data can't be read on this game version. Quest and \
+                 cat-chievement splits are disabled, while the start, load removal and game end keep working",
+            );
+        }
+
+        let memory = Self {
             mono_module,
             mono_image,
-            scene_manager,
+            cat_player,
+            trashcan,
             trashcan_allow_shake,
             is_loading_save,
-            is_teleporting,
             is_outro,
             quest_list,
             quest_secondary_list,
-            post_eat,
-            offset_achievement_id,
-            offset_achievement_completed,
+            achievement_layout,
+            quests_available,
+            quest_list_overflow_logged: Cell::new(false),
+        };
+
+        asr::print_limited::<128>(&format_args!(
+            "    => Journal layout: {}, quests found: {}, cat-chievements found: {}",
+            match journal_instance {
+                true => "instance",
+                false => "static",
+            },
+            memory
+                .read_quests(game, &memory.quest_list)
+                .map_or(0, |list| list.len()),
+            memory
+                .read_quests(game, &memory.quest_secondary_list)
+                .map_or(0, |list| list.len()),
+        ));
+
+        Some(memory)
+    }
+
+    /// Returns whether all the fields of the optional pointers were found at init
+    fn pointers_available(&self) -> bool {
+        self.trashcan_allow_shake.is_some()
+            && self.is_loading_save.is_some()
+            && self.is_outro.is_some()
+    }
+
+    /// Returns the pointer if all of its fields can be found on the current game version.
+    /// Otherwise, the feature using it is logged as disabled, so it never gets read.
+    fn checked_pointer<const CAP: usize>(
+        game: &Process,
+        module: &Module,
+        image: &Image,
+        pointer: UnityPointer<CAP>,
+        feature: &str,
+    ) -> Option<UnityPointer<CAP>> {
+        match pointer.find_missing_field(game, module, image) {
+            Some(name) => {
+                asr::print_limited::<128>(&format_args!(
+                    "    => Feature disabled, missing {name}: {feature}"
+                ));
+                None
+            }
+            _ => Some(pointer),
+        }
+    }
+
+    /// Logs the resolved achievement offsets and quest list addresses, to help
+    /// checking whether a game update moved them
+    fn log_offsets(&self, game: &Process) {
+        let achievement_size = self
+            .mono_image
+            .get_class(game, &self.mono_module, "Achievement")
+            .and_then(|class| class.get_instance_size(game, &self.mono_module));
+
+        asr::print_limited::<256>(&format_args!(
+            "Offsets: Achievement.id = {:#X}, Achievement.completed = {:#X}, \
+             Achievement instance size = {:X?} (read: {:#X})",
+            self.achievement_layout.id,
+            self.achievement_layout.completed,
+            achievement_size,
+            ACHIEVEMENT_SIZE,
+        ));
+
+        for (name, list) in [
+            ("Quest list", &self.quest_list),
+            ("Cat-chievement list", &self.quest_secondary_list),
+        ] {
+            match list.deref::<Address64>(game, &self.mono_module, &self.mono_image) {
+                Some(address) => asr::print_limited::<128>(&format_args!(
+                    "{name} address: {:#X}",
+                    address.value()
+                )),
+                _ => asr::print_limited::<128>(&format_args!("{name} address: not resolved")),
+            }
+        }
+    }
+
+    /// Reads a boolean flag from the game. All the flags tracked by the autosplitter are
+    /// C# `bool` fields, stored as a single byte.
+    fn read_flag<const CAP: usize>(
+        &self,
+        game: &Process,
+        pointer: &UnityPointer<CAP>,
+    ) -> Option<bool> {
+        pointer
+            .deref::<u8>(game, &self.mono_module, &self.mono_image)
+            .map(|val| val != 0)
+    }
+
+    /// Reads the quests contained in the specified list.
+    ///
+    /// Returns `None` if the list can't be read, as opposed to an empty `Vec`
+    /// if the list is actually empty (or quest tracking is disabled).
+    fn read_quests(&self, game: &Process, list: &UnityPointer<2>) -> Option<Vec<QuestData>> {
+        if !self.quests_available {
+            return Some(Vec::with_capacity(0));
+        }
+
+        let list = list.deref::<CSharpList<[u8; ACHIEVEMENT_SIZE]>>(
+            game,
+            &self.mono_module,
+            &self.mono_image,
+        )?;
+
+        match read_quest_list(game, list, &self.achievement_layout) {
+            Ok(quests) => Some(quests),
+            Err(QuestListError::TooLong(count)) => {
+                if !self.quest_list_overflow_logged.replace(true) {
+                    asr::print_limited::<128>(&format_args!(
+                        "  => Quest list reported {} entries (more than {}): read discarded",
+                        count, MAX_QUEST_LIST_LEN
+                    ));
+                }
+                None
+            }
+            Err(QuestListError::Unreadable) => None,
         }
     }
 }
 
+/// Fields of the `CatPlayer` instance.
+///
+/// Instead of resolving a separate pointer path for each field, the instance is
+/// dereferenced once and all the fields are retrieved with a single read of the
+/// memory block covering them.
+struct CatPlayer {
+    instance: UnityPointer<1>,
+    /// Offset of the start of the memory block
+    block_start: u32,
+    /// Buffer the memory block covering all the fields is read into, reused
+    /// on every update instead of allocating a new one
+    block: RefCell<Vec<u8>>,
+
+    is_post_eating: u32,
+    is_teleporting: u32,
+}
+
+/// Values read from the `CatPlayer` instance
+#[derive(Copy, Clone, Default)]
+struct CatPlayerData {
+    is_post_eating: bool,
+    is_teleporting: bool,
+}
+
+impl CatPlayer {
+    fn init(game: &Process, module: &Module, image: &Image) -> Option<Self> {
+        let class = image.get_class(game, module, "CatPlayer")?;
+        let offset = |name: &str| class.get_field_offset(game, module, name);
+
+        let is_post_eating = offset("isPostEating")?;
+        let is_teleporting = offset("isTeleporting")?;
+
+        let block_start = is_post_eating.min(is_teleporting);
+        // Both flags are single bytes
+        let block_end = is_post_eating.max(is_teleporting) + 1;
+
+        Some(Self {
+            instance: UnityPointer::new("CatPlayer", 0, &["_instance"]),
+            block_start,
+            block: RefCell::new(vec![0; (block_end - block_start) as usize]),
+            is_post_eating,
+            is_teleporting,
+        })
+    }
+
+    fn read(&self, game: &Process, module: &Module, image: &Image) -> Option<CatPlayerData> {
+        let instance = self
+            .instance
+            .deref::<Address64>(game, module, image)
+            .filter(|val| !val.is_null())?;
+
+        self.read_at(game, instance)
+    }
+
+    /// Reads the fields of the specified `CatPlayer` instance
+    fn read_at<R: MemoryReader>(&self, reader: &R, instance: Address64) -> Option<CatPlayerData> {
+        let mut block = self.block.borrow_mut();
+        reader.read_into_buf(instance + self.block_start as u64, &mut block)?;
+
+        let index = |offset: u32| (offset - self.block_start) as usize;
+        // All the flags of the cat are C# `bool` fields
+        let flag = |offset: u32| block[index(offset)] != 0;
+
+        Some(CatPlayerData {
+            is_post_eating: flag(self.is_post_eating),
+            is_teleporting: flag(self.is_teleporting),
+        })
+    }
+}
+
 #[derive(Default)]
 struct Watchers {
     start_trigger: Watcher<bool>,
     end_trigger: Watcher<bool>,
+    /// Same as the triggers above, but only set once their flag has been set
+    /// for two consecutive ticks
+    start_trigger_debounced: Watcher<bool>,
+    end_trigger_debounced: Watcher<bool>,
+    /// Whether the game is loading or the cat is teleporting. Used to ignore the
+    /// values restored by a save load.
     is_loading: Watcher<bool>,
+    /// Same as `is_loading`, but excluding teleports
+    is_loading_world: Watcher<bool>,
+    /// Whether the game is loading a save
+    is_loading_save: Watcher<bool>,
+    /// Path of the active scene
+    scene: Watcher<ArrayCString<SCENE_PATH_LEN>>,
+    /// Index in `LOAD_SCENES` of the active scene, if any
+    load_scene: Watcher<Option<usize>>,
+    /// Index in `SPLIT_SCENES` of the active scene, if any
+    split_scene: Watcher<Option<usize>>,
     quest_list: Watcher<Vec<QuestData>>,
     quest_secondary_list: Watcher<Vec<QuestData>>,
+    quest_set: Watcher<QuestSet>,
+    quest_secondary_set: Watcher<QuestSet>,
 
+    in_level: Watcher<bool>,
     is_post_eating: Watcher<bool>,
+    is_teleporting: Watcher<bool>,
     allow_player_shake: Watcher<bool>,
+    /// Whether the trash can the cat starts in could be found
+    trashcan_found: Watcher<bool>,
+    /// Whether the `allowPlayerShake` flag could be read
+    allow_player_shake_read: Watcher<bool>,
+    /// World position of the cat
+    cat_position: Watcher<[f32; 3]>,
 }
 
-fn update_loop(game: &Process, memory: &Memory, watchers: &mut Watchers) {
-    let current_scene = memory.scene_manager.get_current_scene_path::<128>(game);
+/// Values read from the game in a single update.
+///
+/// Reading them is kept apart from updating the watchers, so the update logic
+/// can be fed values that don't come from a live game.
+#[derive(Default)]
+struct Readings {
+    /// Path of the active scene
+    scene: Option<ArrayCString<SCENE_PATH_LEN>>,
+    /// Whether the `allowPlayerShake` flag can be read, ie. Mono is available and the
+    /// fields of the flag exist. Without it, the start falls back to the level scene.
+    start_flag_available: bool,
+    cat_player: Option<CatPlayerData>,
+    cat_position: Option<[f32; 3]>,
+    /// Whether any enabled setting uses the position of the cat
+    cat_position_used: bool,
+    allow_player_shake: Option<bool>,
+    trashcan_found: bool,
+    is_outro: bool,
+    is_loading_save: bool,
+    quest_list: Option<Vec<QuestData>>,
+    quest_secondary_list: Option<Vec<QuestData>>,
+}
 
-    watchers.is_post_eating.update_infallible(
-        memory
-            .post_eat
-            .deref::<u8>(game, &memory.mono_module, &memory.mono_image)
-            .is_some_and(|val| val != 0),
-    );
+impl Readings {
+    fn read(game: &Process, memory: &Memory, settings: &Settings) -> Self {
+        let mono = memory.mono.as_ref();
+        // Same as `mono`, but only if any of the settings using the value is enabled
+        let used_by = |enabled: bool| mono.filter(|_| enabled);
 
-    watchers.allow_player_shake.update_infallible(
-        memory
-            .trashcan_allow_shake
-            .deref::<u8>(game, &memory.mono_module, &memory.mono_image)
-            .is_some_and(|val| val != 0),
-    );
+        let cat_position_used = settings.reset_on_spawn;
 
-    watchers.start_trigger.update_infallible(
-        current_scene
-            .as_ref()
-            .is_some_and(|scene| get_scene_name(scene) == b"Level_X")
-            && watchers
-                .allow_player_shake
-                .pair
-                .is_some_and(|val| val.changed_to(&true)),
-    );
+        Self {
+            scene: memory
+                .scene_manager
+                .get_current_scene_path::<SCENE_PATH_LEN>(game),
 
-    watchers.end_trigger.update_infallible(
-        memory
-            .is_outro
-            .deref::<u8>(game, &memory.mono_module, &memory.mono_image)
-            .is_some_and(|val| val != 0),
-    );
+            start_flag_available: mono.is_some_and(|mono| mono.trashcan_allow_shake.is_some()),
 
-    watchers.is_loading.update_infallible(
-        current_scene.as_ref().is_some_and(|scene| {
-            let scene_name = get_scene_name(scene);
-            scene_name == b"Loading" || scene_name == b"MainMenu_LKBC"
-        }) || memory
-            .is_loading_save
-            .deref::<u8>(game, &memory.mono_module, &memory.mono_image)
-            .is_some_and(|val| val != 0)
-            || memory
-                .is_teleporting
-                .deref::<u8>(game, &memory.mono_module, &memory.mono_image)
-                .is_some_and(|val| val != 0),
-    );
+            cat_player: mono.and_then(|mono| {
+                mono.cat_player
+                    .read(game, &mono.mono_module, &mono.mono_image)
+            }),
 
-    watchers.quest_list.update_infallible({
-        match memory
-            .quest_list
-            .deref::<CSharpList<[u8; 0x68]>>(game, &memory.mono_module, &memory.mono_image)
-            .map(|list| list.iter(game))
-            .map(|data| {
-                data.map(|item| QuestData {
-                    quest_id: unsafe {
-                        *(item.as_ptr().byte_add(memory.offset_achievement_id) as *const u32)
-                    },
-                    complete: item[memory.offset_achievement_completed] != 0,
-                })
-            }) {
-            Some(x) => x.collect(),
-            _ => Vec::with_capacity(0),
-        }
-    });
+            // The cat's position is taken from its Transform
+            cat_position: used_by(cat_position_used).and_then(|mono| {
+                mono.cat_player
+                    .instance
+                    .deref::<Address64>(game, &mono.mono_module, &mono.mono_image)
+                    .and_then(|instance| {
+                        Transform::from_component(
+                            game,
+                            memory.scene_manager.pointer_size(),
+                            instance,
+                        )
+                    })
+                    .and_then(|transform| transform.position(game))
+            }),
 
-    watchers.quest_secondary_list.update_infallible({
-        match memory
-            .quest_secondary_list
-            .deref::<CSharpList<[u8; 0x68]>>(game, &memory.mono_module, &memory.mono_image)
-            .map(|list| list.iter(game))
-            .map(|data| {
-                data.map(|item| QuestData {
-                    quest_id: unsafe {
-                        *(item.as_ptr().byte_add(memory.offset_achievement_id) as *const u32)
-                    },
-                    complete: item[memory.offset_achievement_completed] != 0,
+            cat_position_used,
+
+            allow_player_shake: mono
+                .and_then(|mono| mono.read_flag(game, mono.trashcan_allow_shake.as_ref()?)),
+
+            trashcan_found: mono
+                .and_then(|mono| {
+                    mono.trashcan.as_ref()?.deref::<Address64>(
+                        game,
+                        &mono.mono_module,
+                        &mono.mono_image,
+                    )
                 })
-            }) {
-            Some(x) => x.collect(),
-            _ => Vec::with_capacity(0),
-        }
-    });
-}
+                .is_some_and(|val| !val.is_null()),
 
-fn start(watchers: &Watchers, settings: &Settings) -> bool {
-    settings.start
-        && watchers
-            .start_trigger
-            .pair
-            .is_some_and(|val| val.changed_to(&true))
-}
+            is_outro: mono
+                .and_then(|mono| mono.read_flag(game, mono.is_outro.as_ref()?))
+                .unwrap_or_default(),
 
-fn split(watchers: &Watchers, settings: &Settings) -> bool {
-    let end_trigger = settings.got_home
-        && watchers
-            .end_trigger
-            .pair
-            .is_some_and(|val| val.changed_to(&true));
+            is_loading_save: mono
+                .and_then(|mono| mono.read_flag(game, mono.is_loading_save.as_ref()?))
+                .unwrap_or_default(),
 
-    let quest_list = {
-        let mut value = false;
-
-        if let Some(quest) = &watchers.quest_list.pair {
-            for i in &quest.current {
-                let quest_id = i.quest_id;
-
-                let split_setting = match quest_id {
-                    8 => settings.catch_a_bird,
-                    12 => settings.fetch_dog_balls,
-                    19 => settings.bring_crow_25_shinies,
-                    21 => settings.rescue_tanuki,
-                    24 => settings.fetch_3_feathers,
-                    28 => settings.reunite_the_family,
-                    29 => settings.help_mayor,
-                    32 => settings.find_crow,
-                    34 => settings.become_artist,
-                    36 => settings.find_chameleon_1,
-                    37 => settings.find_chameleon_2,
-                    38 => settings.find_chameleon_3,
-                    39 => settings.sunbeam,
-                    49 => settings.pose_for_beetle,
-                    41 => settings.find_chameleon_4,
-                    42 => settings.find_chameleon_5,
-                    43 => settings.find_chameleon_6,
-                    44 => settings.find_chameleon_7,
-                    45 => settings.find_chameleon_8,
-                    47 => settings.steal_lunch,
-                    56 => settings.catch_yellow_bird,
-                    _ => false,
-                };
-
-                if split_setting {
-                    let old = quest
-                        .old
-                        .iter()
-                        .find(|&val| val.quest_id.eq(&quest_id))
-                        .map(|val| val.complete);
-
-                    if old.is_some_and(|val| !val) && i.complete {
-                        value = true;
-                        break;
-                    }
-                }
-            }
+            quest_list: match mono {
+                Some(mono) => mono.read_quests(game, &mono.quest_list),
+                _ => Some(Vec::with_capacity(0)),
+            },
+
+            quest_secondary_list: match mono {
+                Some(mono) => mono.read_quests(game, &mono.quest_secondary_list),
+                _ => Some(Vec::with_capacity(0)),
+            },
         }
+    }
+}
 
-        value
-    };
+impl Watchers {
+    /// Updates the watchers with the values read in the current update
+    fn update(&mut self, readings: Readings) {
+        let current_scene = readings.scene;
+        let cat_player = readings.cat_player;
 
-    let catchievements = {
-        let mut value = false;
-
-        if let Some(quest) = &watchers.quest_secondary_list.pair {
-            for i in &quest.current {
-                let quest_id = i.quest_id;
-
-                let split_setting = match quest_id {
-                    1 => settings.hello_everyone,
-                    2 => settings.quack_troops,
-                    3 => settings.snap_happy,
-                    7 => settings.capped_crusader,
-                    8 => settings.world_traveler,
-                    9 => settings.cat_napper,
-                    10 => settings.bird_botherer,
-                    11 => settings.if_i_fits_i_sits,
-                    12 => settings.litter_picker,
-                    13 => settings.smash_hit,
-                    14 => settings.sticky_business,
-                    15 => settings.give_a_dog_a_bone,
-                    16 => settings.cult_of_purrsonality,
-                    17 => settings.local_celebrity,
-                    19 => settings.papa_cat_zi,
-                    23 => settings.cat_like_reflexes,
-                    24 => settings.back_of_the_net,
-                    26 => settings.surprise,
-                    27 => settings.fruit_fall,
-                    30 => settings.industrial_artist,
-                    31 => settings.checkmate,
-                    32 => settings.to_me_to_you,
-                    33 => settings.no_parking,
-                    34 => settings.rub_a_dub_dub,
-                    36 => settings.and_stay_out,
-                    37 => settings.killer_kitty,
-                    38 => settings.who_needs_cash,
-                    39 => settings.little_kitty_big_city,
-                    41 => settings.cant_stop_the_feelings,
-                    42 => settings.what_sweet_music,
-                    43 => settings.trip_hazard,
-                    44 => settings.splish,
-                    45 => settings.decluttering,
-                    46 => settings.dumpster_diving,
-                    _ => false,
-                };
-
-                if split_setting {
-                    let old = quest
-                        .old
-                        .iter()
-                        .find(|&val| val.quest_id.eq(&quest_id))
-                        .map(|val| val.complete);
-
-                    if old.is_some_and(|val| !val) && i.complete {
-                        value = true;
-                        break;
-                    }
+        self.in_level.update_infallible(
+            current_scene
+                .as_ref()
+                .is_some_and(|scene| get_scene_name(scene) == b"Level_X"),
+        );
+
+        self.is_post_eating.update_infallible(
+            cat_player
+                .map(|data| data.is_post_eating)
+                .unwrap_or_default(),
+        );
+
+        self.is_teleporting
+            .update(cat_player.map(|data| data.is_teleporting));
+
+        // The last known position is kept while it can't be read, eg. during loads
+        let last_position = self.cat_position.pair.map(|val| val.current);
+        self.cat_position.update(
+            readings
+                .cat_position
+                .or(last_position.filter(|_| readings.cat_position_used)),
+        );
+
+        self.allow_player_shake
+            .update_infallible(readings.allow_player_shake.unwrap_or_default());
+
+        self.allow_player_shake_read
+            .update_infallible(readings.allow_player_shake.is_some());
+
+        self.trashcan_found
+            .update_infallible(readings.trashcan_found);
+
+        // Without the flag, the start falls back to the level scene being loaded
+        self.start_trigger
+            .update_infallible(match readings.start_flag_available {
+                true => {
+                    self.in_level.pair.is_some_and(|val| val.current)
+                        && self
+                            .allow_player_shake
+                            .pair
+                            .is_some_and(|val| val.changed_to(&true))
                 }
+                false => self.in_level.pair.is_some_and(|val| val.changed_to(&true)),
+            });
+
+        // The debounced start fires on the tick after the start flag got set, if it's still set
+        let start_trigger = self.start_trigger.pair.is_some_and(|val| val.old);
+        self.start_trigger_debounced.update_infallible(
+            start_trigger
+                && match readings.start_flag_available {
+                    true => self.allow_player_shake.pair.is_some_and(|val| val.current),
+                    false => self.in_level.pair.is_some_and(|val| val.current),
+                },
+        );
+
+        self.end_trigger.update_infallible(readings.is_outro);
+
+        self.end_trigger_debounced.update_infallible(
+            self.end_trigger
+                .pair
+                .is_some_and(|val| val.old && val.current),
+        );
+
+        self.scene.update(current_scene);
+
+        self.load_scene
+            .update_infallible(current_scene.as_ref().and_then(|scene| {
+                let scene_name = get_scene_name(scene);
+                LOAD_SCENES.iter().position(|&(name, _)| name == scene_name)
+            }));
+
+        self.split_scene
+            .update_infallible(current_scene.as_ref().and_then(|scene| {
+                let scene_name = get_scene_name(scene);
+                SPLIT_SCENES
+                    .iter()
+                    .position(|&(name, _)| name == scene_name)
+            }));
+
+        self.is_loading_save
+            .update_infallible(readings.is_loading_save);
+
+        self.is_loading_world.update_infallible(
+            current_scene.as_ref().is_some_and(|scene| {
+                let scene_name = get_scene_name(scene);
+                scene_name == LOADING_SCENE || scene_name == MAIN_MENU_SCENE
+            }) || readings.is_loading_save,
+        );
+
+        self.is_loading.update_infallible(
+            self.is_loading_world.pair.is_some_and(|val| val.current)
+                || cat_player.is_some_and(|data| data.is_teleporting),
+        );
+
+        update_quest_list(&mut self.quest_list, readings.quest_list);
+        update_quest_list(
+            &mut self.quest_secondary_list,
+            readings.quest_secondary_list,
+        );
+
+        update_quest_set(&mut self.quest_set, &self.quest_list);
+        update_quest_set(&mut self.quest_secondary_set, &self.quest_secondary_list);
+    }
+}
+
+/// Updates a quest set watcher from its quest list. The set is only rebuilt when
+/// the list changed, otherwise the last set is carried over.
+fn update_quest_set(set: &mut Watcher<QuestSet>, list: &Watcher<Vec<QuestData>>) {
+    let current = match (&list.pair, &set.pair) {
+        (Some(list), Some(set)) if !list.changed() => set.current,
+        (Some(list), _) => QuestSet::new(&list.current),
+        (None, _) => QuestSet::default(),
+    };
+    set.update_infallible(current);
+}
+
+/// Updates a quest list watcher. If the list couldn't be read, the last value is
+/// kept instead, so a transient read failure doesn't look like an empty list.
+fn update_quest_list(watcher: &mut Watcher<Vec<QuestData>>, quests: Option<Vec<QuestData>>) {
+    let quests = quests
+        .or_else(|| watcher.pair.as_ref().map(|val| val.current.clone()))
+        .unwrap_or_default();
+    watcher.update_infallible(quests);
+}
+
+impl Watchers {
+    fn quest_set(&self, list: QuestList) -> Option<&Pair<QuestSet>> {
+        match list {
+            QuestList::Main => self.quest_set.pair.as_ref(),
+            QuestList::Secondary => self.quest_secondary_set.pair.as_ref(),
+        }
+    }
+
+    /// Returns whether the active scene is listed in `IGNORED_SCENES`
+    fn in_ignored_scene(&self) -> bool {
+        self.scene
+            .pair
+            .as_ref()
+            .is_some_and(|val| IGNORED_SCENES.contains(&get_scene_name(&val.current)))
+    }
+
+    /// Returns whether the specified quest got completed in the current update.
+    /// Quests missing from the previous update don't count as newly completed.
+    fn is_quest_newly_completed(&self, list: QuestList, quest_id: u32) -> bool {
+        self.quest_set(list).is_some_and(|set| {
+            set.old.is_complete(quest_id) == Some(false)
+                && set.current.is_complete(quest_id) == Some(true)
+        })
+    }
+
+    /// Returns the quests enabled in the settings that are not complete yet
+    fn incomplete_quests<'a>(
+        &'a self,
+        settings: &'a Settings,
+    ) -> impl Iterator<Item = (QuestList, u32)> + 'a {
+        [
+            (QuestList::Main, &self.quest_list),
+            (QuestList::Secondary, &self.quest_secondary_list),
+        ]
+        .into_iter()
+        .flat_map(move |(list, watcher)| {
+            watcher
+                .pair
+                .iter()
+                .flat_map(|pair| pair.current.iter())
+                .filter(move |quest| !quest.complete && list.is_enabled(settings, quest.quest_id))
+                .map(move |quest| (list, quest.quest_id))
+        })
+    }
+}
+
+fn update_run_state(watchers: &Watchers, settings: &Settings, state: &mut RunState) {
+    let timer_state = timer_state(settings, state);
+
+    // Game time and load time are tracked separately from the timer, as they can't be queried back
+    if timer_state == TimerState::Running {
+        if let Some(last_tick) = state.last_tick {
+            match is_loading(watchers, settings) {
+                Some(true) => state.load_time += last_tick.elapsed(),
+                _ => state.game_time += last_tick.elapsed(),
             }
         }
+    }
+    state.last_tick = Some(Instant::now());
 
-        value
-    };
+    if settings.log_pauses && timer_state != TimerState::NotRunning {
+        let cause = pause_cause(watchers, settings);
+
+        if state.game_time_paused != Some(cause.is_some()) {
+            state.game_time_paused = Some(cause.is_some());
+
+            asr::print_limited::<256>(&format_args!(
+                "Game time {} ({}) in {} @ {}:{:02}.{:03}",
+                match cause {
+                    Some(_) => "paused",
+                    None => "resumed",
+                },
+                cause.unwrap_or("no pause condition"),
+                watchers
+                    .scene
+                    .pair
+                    .as_ref()
+                    .and_then(|val| core::str::from_utf8(get_scene_name(&val.current)).ok())
+                    .unwrap_or("-"),
+                state.game_time.whole_minutes(),
+                state.game_time.whole_seconds() % 60,
+                state.game_time.subsec_milliseconds(),
+            ));
+        }
+    }
+
+    track_run_state(watchers, state, timer_state);
+}
+
+/// Updates the parts of the run state that only depend on the watchers and on the
+/// state of the timer
+fn track_run_state(watchers: &Watchers, state: &mut RunState, timer_state: TimerState) {
+    // The timer can also be reset by the runner, so nothing from the previous
+    // run is allowed to leak into the next one
+    if timer_state == TimerState::NotRunning {
+        state.reset();
+    }
+
+    if let Some(scene) = watchers.scene.pair.filter(|val| val.changed()) {
+        state.last_scene = Some(scene.old);
+    }
+
+    if timer_state == TimerState::Running {
+        for entry in QUESTS
+            .iter()
+            .filter(|entry| watchers.is_quest_newly_completed(entry.list, entry.quest_id))
+        {
+            state
+                .quest_times
+                .push((entry.list, entry.quest_id, state.game_time));
+        }
+    }
+
+    // A fresh world load goes through the loading scene right before the level
+    if watchers
+        .in_level
+        .pair
+        .is_some_and(|val| val.changed_to(&true))
+        && watchers.load_scene.pair.is_some_and(|val| {
+            val.old
+                .and_then(|index| LOAD_SCENES.get(index))
+                .is_some_and(|&(name, _)| name == LOADING_SCENE)
+        })
+    {
+        state.start_armed = true;
+    }
+
+    state.start_cooldown_ticks = state.start_cooldown_ticks.saturating_sub(1);
+}
+
+/// Publishes the variables. On the first update, the ones only published on change
+/// get published regardless.
+/// Variables shown to the user, only published again when their value changes.
+///
+/// Most of them keep the same value for long stretches of time, so this avoids
+/// sending them to the timer on every tick.
+#[derive(Default)]
+struct Variables {
+    /// Last value published for each variable, by name
+    published: Vec<(&'static str, String)>,
+    /// Buffer the formatted values are written into, reused on every update
+    buffer: String,
+}
+
+impl Variables {
+    /// Publishes the variable, unless it already has the specified value
+    fn set(&mut self, name: &'static str, value: &str) {
+        match self.published.iter_mut().find(|(key, _)| *key == name) {
+            Some((_, published)) if published == value => return,
+            Some((_, published)) => {
+                published.clear();
+                published.push_str(value);
+            }
+            _ => self.published.push((name, String::from(value))),
+        }
+        timer::set_variable(name, value);
+    }
+
+    /// Same as [`set`](Self::set), but formats the value into the reused buffer first
+    fn set_fmt(&mut self, name: &'static str, args: fmt::Arguments<'_>) {
+        let mut buffer = core::mem::take(&mut self.buffer);
+        buffer.clear();
+        // Writing into a `String` can't fail
+        let _ = buffer.write_fmt(args);
+        self.set(name, &buffer);
+        self.buffer = buffer;
+    }
+}
+
+fn update_variables(
+    variables: &mut Variables,
+    watchers: &Watchers,
+    settings: &Settings,
+    state: &RunState,
+) {
+    variables.set_fmt(
+        "Loads",
+        format_args!(
+            "{}:{:02}",
+            state.load_time.whole_minutes(),
+            state.load_time.whole_seconds() % 60
+        ),
+    );
+
+    variables.set_fmt("Splits", format_args!("{}", state.splits_fired));
+
+    let segment_time = state.game_time - state.last_split_game_time;
+    variables.set_fmt(
+        "Segment",
+        format_args!(
+            "{}:{:02}.{}",
+            segment_time.whole_minutes(),
+            segment_time.whole_seconds() % 60,
+            segment_time.subsec_milliseconds() / 100
+        ),
+    );
+
+    variables.set(
+        "Last quest",
+        state
+            .last_completed_quest
+            .and_then(|(list, quest_id)| list.name(quest_id))
+            .unwrap_or("-"),
+    );
+
+    match state.quest_times.last() {
+        Some(&(list, quest_id, time)) => variables.set_fmt(
+            "Last quest time",
+            format_args!(
+                "{} @ {}:{:02}",
+                list.name(quest_id).unwrap_or("unknown"),
+                time.whole_minutes(),
+                time.whole_seconds() % 60
+            ),
+        ),
+        _ => variables.set("Last quest time", "-"),
+    }
+
+    for (name, scene) in [
+        ("Scene", watchers.scene.pair.map(|val| val.current)),
+        ("Last scene", state.last_scene),
+    ] {
+        variables.set(
+            name,
+            scene
+                .as_ref()
+                .and_then(|scene| core::str::from_utf8(get_scene_name(scene)).ok())
+                .unwrap_or("-"),
+        );
+    }
+
+    variables.set(
+        "Next",
+        watchers
+            .incomplete_quests(settings)
+            .find_map(|(list, quest_id)| list.name(quest_id))
+            .unwrap_or("-"),
+    );
+
+    for (name, set) in [
+        ("Quests", &watchers.quest_set),
+        ("Cat-chievements", &watchers.quest_secondary_set),
+    ] {
+        if let Some(val) = &set.pair {
+            variables.set_fmt(
+                name,
+                format_args!("{}/{}", val.current.completed_count(), val.current.len()),
+            );
+        }
+    }
+
+    if settings.show_start_trigger_state {
+        variables.set(
+            "Trash can",
+            match watchers.trashcan_found.pair.is_some_and(|val| val.current) {
+                true => "Found",
+                false => "Not found",
+            },
+        );
+
+        variables.set(
+            "Shake allowed",
+            match (
+                watchers.allow_player_shake_read.pair.map(|val| val.current),
+                watchers.allow_player_shake.pair.map(|val| val.current),
+            ) {
+                (Some(true), Some(true)) => "Yes",
+                (Some(true), _) => "No",
+                _ => "Unreadable",
+            },
+        );
+    }
+}
+
+fn start(watchers: &Watchers, settings: &Settings, state: &RunState) -> bool {
+    if state.start_cooldown_ticks > 0 || watchers.in_ignored_scene() {
+        return false;
+    }
+
+    settings.start
+        && (state.start_armed || !settings.start_requires_world_load)
+        && match settings.debounce_start {
+            true => &watchers.start_trigger_debounced,
+            false => &watchers.start_trigger,
+        }
+        .pair
+        .is_some_and(|val| val.changed_to(&true))
+}
+
+fn split(watchers: &Watchers, settings: &Settings, state: &mut RunState) -> Option<SplitEvent> {
+    // Loading a save right after the start can pre-populate completed quests, so
+    // nothing new is triggered until the run has been going for long enough, nor
+    // in non-gameplay scenes. The splits already queued are still fired, and the
+    // cooldowns keep running.
+    if watchers.in_ignored_scene() || state.game_time < settings.min_run_seconds.duration() {
+        state.advance_cooldowns();
+        return state.pending_splits.pop_front();
+    }
+
+    let end_trigger = settings.got_home
+        && match settings.game_end_source {
+            GameEndSource::OutroFlag => match settings.debounce_end {
+                true => &watchers.end_trigger_debounced,
+                false => &watchers.end_trigger,
+            }
+            .pair
+            .is_some_and(|val| val.changed_to(&true)),
+            GameEndSource::GotHomeAchievement => {
+                watchers.is_quest_newly_completed(QuestList::Secondary, GOT_HOME_ACHIEVEMENT)
+            }
+        };
+
+    // Quests missing from the old set (eg. the list was empty) never count as newly completed
+    let chameleons_found = watchers.quest_set.pair.is_some_and(|set| {
+        set.old.all_complete(&CHAMELEON_QUESTS) == Some(false)
+            && set.current.all_complete(&CHAMELEON_QUESTS) == Some(true)
+    });
+
+    // The outro can be replayed, so by default the game end only splits once per run
+    let game_end = match settings.end_on_last_chameleon {
+        true => chameleons_found,
+        false => end_trigger,
+    } && (settings.allow_end_retrigger || !state.game_end_done);
+
+    if game_end {
+        state.game_end_done = true;
+
+        if settings.log_quest_times {
+            for &(list, quest_id, time) in &state.quest_times {
+                asr::print_limited::<128>(&format_args!(
+                    "{} completed at {}:{:02}.{:03}",
+                    list.name(quest_id).unwrap_or("unknown"),
+                    time.whole_minutes(),
+                    time.whole_seconds() % 60,
+                    time.subsec_milliseconds(),
+                ));
+            }
+        }
+    }
+
+    let mut quest_list = watchers
+        .quest_list
+        .pair
+        .as_ref()
+        .map(|quest| QuestList::Main.newly_completed(settings, quest))
+        .unwrap_or_default();
+
+    // Checked against the full lists, regardless of the quests enabled in the settings
+    let hundred_percent = settings.split_on_100_percent
+        && !state.hundred_percent_done
+        && watchers
+            .quest_set
+            .pair
+            .zip(watchers.quest_secondary_set.pair)
+            .is_some_and(|(main, secondary)| {
+                // An empty old set means the lists just got read, eg. when loading a save
+                main.old.len() > 0
+                    && secondary.old.len() > 0
+                    && !(main.old.all_quests_complete() && secondary.old.all_quests_complete())
+                    && main.current.all_quests_complete()
+                    && secondary.current.all_quests_complete()
+            });
+
+    if hundred_percent {
+        state.hundred_percent_done = true;
+    }
+
+    // Tracked regardless of the fish split settings
+    if watchers
+        .is_post_eating
+        .pair
+        .is_some_and(|val| val.changed_to(&true))
+    {
+        state.fish_eaten = true;
+    }
+
+    // An empty old set means the list just got read, eg. when loading a save
+    let quests_and_fish = settings.split_on_quests_and_fish
+        && !state.quests_and_fish_done
+        && state.fish_eaten
+        && watchers
+            .quest_set
+            .pair
+            .is_some_and(|set| set.old.len() > 0 && set.current.all_quests_complete());
+
+    if quests_and_fish {
+        state.quests_and_fish_done = true;
+    }
+
+    // In the chameleon category, finding all the chameleons is the game end instead
+    let all_chameleons =
+        settings.split_on_all_chameleons && !settings.end_on_last_chameleon && chameleons_found;
+
+    let scene = watchers
+        .split_scene
+        .pair
+        .filter(|val| val.changed())
+        .and_then(|val| match settings.split_on_scene_exit {
+            true => val.old,
+            false => val.current,
+        })
+        .filter(|&index| {
+            state.scenes_split & (1 << index) == 0
+                && SPLIT_SCENES
+                    .get(index)
+                    .is_some_and(|&(_, enabled)| enabled(settings))
+        });
+
+    if let Some(index) = scene {
+        state.scenes_split |= 1 << index;
+    }
+    let scene = scene.is_some();
+
+    let teleport = state.teleport_cooldown.check(
+        settings.split_on_teleport
+            && watchers
+                .is_teleporting
+                .pair
+                .is_some_and(|val| val.changed_to(&true)),
+    );
+
+    let mut catchievements = watchers
+        .quest_secondary_list
+        .pair
+        .as_ref()
+        .map(|quest| QuestList::Secondary.newly_completed(settings, quest))
+        .unwrap_or_default();
+
+    // Tracked regardless of whether the completion actually triggers a split
+    if let Some(last) = quest_list
+        .last()
+        .map(|&quest_id| (QuestList::Main, quest_id))
+        .or(catchievements
+            .last()
+            .map(|&quest_id| (QuestList::Secondary, quest_id)))
+    {
+        state.last_completed_quest = Some(last);
+    }
+
+    // In the chameleon category, the last chameleon found splits as the game end only
+    quest_list.retain(|quest_id| {
+        !(game_end && settings.end_on_last_chameleon && CHAMELEON_QUESTS.contains(quest_id))
+    });
+
+    // When used as the signal of the game end, the got home cat-chievement splits as
+    // the game end only
+    catchievements.retain(|&quest_id| {
+        !(quest_id == GOT_HOME_ACHIEVEMENT
+            && settings.got_home
+            && !settings.end_on_last_chameleon
+            && settings.game_end_source == GameEndSource::GotHomeAchievement)
+    });
+
+    // In this mode, only the first quest or cat-chievement that gets completed triggers
+    // a split. Completions within the merge window of the last quest split don't trigger
+    // a split of their own, and the ones at the same time are merged into one split.
+    let within_merge_window = settings
+        .quest_merge_window
+        .duration()
+        .zip(state.last_quest_split_game_time)
+        .is_some_and(|(window, time)| state.game_time - time < window);
+    if (settings.split_only_first_quest && state.quest_split_done) || within_merge_window {
+        quest_list.clear();
+        catchievements.clear();
+    } else if settings.split_only_first_quest || settings.quest_merge_window.duration().is_some() {
+        quest_list.truncate(1);
+        if !quest_list.is_empty() {
+            catchievements.clear();
+        }
+        catchievements.truncate(1);
+    }
+
+    if !quest_list.is_empty() || !catchievements.is_empty() {
+        state.quest_split_done = true;
+        state.last_quest_split_game_time = Some(state.game_time);
+    }
 
     let post_eating = settings.eat_fish
         && watchers
@@ -620,23 +1939,1695 @@ fn split(watchers: &Watchers, settings: &Settings) -> bool {
             .pair
             .is_some_and(|val| val.changed_to(&true));
 
-    end_trigger || quest_list || catchievements || post_eating
+    let game_end = game_end.then_some(SplitEvent::Other(match settings.game_end_source {
+        _ if settings.end_on_last_chameleon => "game end (all chameleons found)",
+        GameEndSource::OutroFlag => "game end (outro flag)",
+        GameEndSource::GotHomeAchievement => "game end (got home cat-chievement)",
+    }));
+
+    let quests = quest_list
+        .into_iter()
+        .map(|quest_id| SplitEvent::Quest(QuestList::Main, quest_id))
+        .chain(
+            catchievements
+                .into_iter()
+                .map(|quest_id| SplitEvent::Quest(QuestList::Secondary, quest_id)),
+        );
+
+    // Every event triggers a split of its own. The ones triggered in the same update
+    // are fired one per tick: quest completions, then the fish, or
+    // the other way around depending on `split_priority`, then the other events. The
+    // game end always comes last, as any split queued after it would be lost once the
+    // run is over.
+    let other = |triggered: bool, description| triggered.then_some(SplitEvent::Other(description));
+    let fish = other(post_eating, "fish eaten");
+    let (fish_first, fish_after_quests) = match settings.split_priority {
+        SplitPriority::QuestsFirst => (None, fish),
+        SplitPriority::FishFirst => (fish, None),
+    };
+    let triggered = [
+        other(all_chameleons, "all chameleons found"),
+        other(scene, "scene change"),
+        other(hundred_percent, "100% completed"),
+        other(quests_and_fish, "all main quests and fish eaten"),
+        other(teleport, "portal used"),
+        game_end,
+    ];
+
+    for event in fish_first
+        .into_iter()
+        .chain(quests)
+        .chain(fish_after_quests)
+        .chain(triggered.into_iter().flatten())
+    {
+        if settings.log_splits {
+            asr::print_limited::<128>(&format_args!("Split triggered: {event}"));
+        }
+        state.pending_splits.push_back(event);
+    }
+    state.pending_splits.pop_front()
+}
+
+/// Logs every tracked quest and cat-chievement, along with whether splitting on it is enabled
+fn log_quest_settings(settings: &Settings) {
+    for list in [QuestList::Main, QuestList::Secondary] {
+        for (quest_id, enabled) in list.tracked_quests(settings) {
+            asr::print_limited::<128>(&format_args!(
+                "{} {} ({}): {}",
+                match list {
+                    QuestList::Main => "Quest",
+                    QuestList::Secondary => "Cat-chievement",
+                },
+                quest_id,
+                list.name(quest_id).unwrap_or("unknown"),
+                match enabled {
+                    true => "enabled",
+                    false => "disabled",
+                },
+            ));
+        }
+    }
+}
+
+/// Logs the splits enabled in the current settings, one per line. Quests are listed
+/// in the order of the settings, which doesn't necessarily match the route.
+fn log_splits_template(settings: &Settings) {
+    asr::print_message("Splits template:");
+
+    for (index, name) in splits_template(settings).into_iter().enumerate() {
+        asr::print_limited::<128>(&format_args!("  {}. {}", index + 1, name));
+    }
+
+    // If only some of the chameleons split, whether the last one found is among them
+    // can't be known in advance
+    let chameleons_enabled = CHAMELEON_QUESTS
+        .iter()
+        .filter(|&&quest_id| QuestList::Main.is_enabled(settings, quest_id))
+        .count();
+    if settings.end_on_last_chameleon
+        && !settings.split_only_first_quest
+        && (1..CHAMELEON_QUESTS.len()).contains(&chameleons_enabled)
+    {
+        asr::print_message("  (minus one if the last chameleon found is listed above)");
+    }
+
+    // These can trigger any number of times, so they can't be listed in advance
+    for (enabled, name) in [
+        (settings.eat_fish, "fish eaten"),
+        (settings.split_on_teleport, "portal use"),
+    ] {
+        if enabled {
+            asr::print_limited::<128>(&format_args!("  + one split per {}", name));
+        }
+    }
+}
+
+/// Returns the splits triggered once per run with the current settings, in the order
+/// they are logged by `log_splits_template`
+fn splits_template(settings: &Settings) -> Vec<&'static str> {
+    // In this mode, the quests are all merged in a single split
+    let first_quest_only = settings.split_only_first_quest;
+
+    // When all the chameleons end the run, the last one found only splits as the game
+    // end. As any of them can be the last one, they are listed without their names.
+    let last_chameleon = CHAMELEON_QUESTS
+        .iter()
+        .all(|&quest_id| QuestList::Main.is_enabled(settings, quest_id))
+        .then_some(CHAMELEON_QUESTS[CHAMELEON_QUESTS.len() - 1])
+        .filter(|_| settings.end_on_last_chameleon);
+
+    let got_home_is_game_end = settings.got_home
+        && !settings.end_on_last_chameleon
+        && settings.game_end_source == GameEndSource::GotHomeAchievement;
+
+    // Same filters as the ones applied to the quest completions when splitting
+    let quests = QUESTS
+        .iter()
+        .filter(|entry| !first_quest_only && (entry.setting)(settings))
+        .filter(|entry| match entry.list {
+            QuestList::Main => Some(entry.quest_id) != last_chameleon,
+            QuestList::Secondary => {
+                !(entry.quest_id == GOT_HOME_ACHIEVEMENT && got_home_is_game_end)
+            }
+        })
+        .map(|entry| match entry.list {
+            QuestList::Main
+                if settings.end_on_last_chameleon && CHAMELEON_QUESTS.contains(&entry.quest_id) =>
+            {
+                "Chameleon found"
+            }
+            _ => entry.name,
+        })
+        .chain(first_quest_only.then_some("First quest or cat-chievement"));
+
+    let once = [
+        (
+            settings.split_on_all_chameleons && !settings.end_on_last_chameleon,
+            "All chameleons",
+        ),
+        (settings.split_on_100_percent, "100%"),
+        (settings.split_on_quests_and_fish, "All quests and fish"),
+        (
+            settings.got_home || settings.end_on_last_chameleon,
+            "Game end",
+        ),
+    ]
+    .into_iter()
+    .filter(|&(enabled, _)| enabled)
+    .map(|(_, name)| name);
+
+    let scenes = SPLIT_SCENES
+        .iter()
+        .filter(|&&(_, enabled)| enabled(settings))
+        .filter_map(|&(name, _)| core::str::from_utf8(name).ok());
+
+    quests.chain(scenes).chain(once).collect()
+}
+
+fn reset(watchers: &Watchers, settings: &Settings, state: &RunState) -> bool {
+    // The spawn point is only known for runs started by the autosplitter
+    let Some(spawn) = state.spawn_position else {
+        return false;
+    };
+
+    // The spawn point is also reached shortly after the start, so the reset is
+    // only allowed after the same grace window used for the splits. Walking back
+    // to the spawn point is not a respawn: the cat has to snap there in one update,
+    // without going through a portal.
+    settings.reset_on_spawn
+        && state.game_time >= settings.min_run_seconds.duration().max(SPAWN_RESET_GRACE)
+        && watchers
+            .is_teleporting
+            .pair
+            .is_none_or(|val| !val.old && !val.current)
+        && watchers.cat_position.pair.is_some_and(|val| {
+            !is_near_spawn(spawn, val.old)
+                && is_near_spawn(spawn, val.current)
+                && distance_squared(val.old, val.current)
+                    > SPAWN_SNAP_DISTANCE * SPAWN_SNAP_DISTANCE
+        })
 }
 
-fn reset(_watchers: &Watchers, _settings: &Settings) -> bool {
-    false
+/// Returns whether the specified position is within `SPAWN_RADIUS` of the spawn point
+fn is_near_spawn(spawn: [f32; 3], position: [f32; 3]) -> bool {
+    distance_squared(spawn, position) < SPAWN_RADIUS * SPAWN_RADIUS
 }
 
-fn is_loading(watchers: &Watchers, _settings: &Settings) -> Option<bool> {
-    Some(watchers.is_loading.pair.is_some_and(|val| val.eq(&true)))
+/// Returns the squared distance between two positions
+fn distance_squared(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum()
+}
+
+fn is_loading(watchers: &Watchers, settings: &Settings) -> Option<bool> {
+    Some(pause_cause(watchers, settings).is_some())
+}
+
+/// Returns the reason why the game time should be paused, or `None` if it should be running
+fn pause_cause(watchers: &Watchers, settings: &Settings) -> Option<&'static str> {
+    // Scenes listed in LOAD_SCENES follow their own setting. Keeping game time running
+    // in them only cancels the other loads if explicitly asked to.
+    if let Some(&(_, pause)) = watchers
+        .load_scene
+        .pair
+        .and_then(|val| val.current)
+        .and_then(|index| LOAD_SCENES.get(index))
+    {
+        match pause(settings) {
+            true => return Some("load scene"),
+            false if settings.load_scenes_override => return None,
+            false => {}
+        }
+    }
+
+    // Some leaderboards count the portal travel as gameplay
+    if settings.pause_during_teleport && watchers.is_teleporting.pair.is_some_and(|val| val.current)
+    {
+        return Some("teleporting");
+    }
+
+    watchers
+        .is_loading_save
+        .pair
+        .is_some_and(|val| val.current)
+        .then_some("loading flag")
 }
 
 fn game_time(_watchers: &Watchers, _settings: &Settings, _addresses: &Memory) -> Option<Duration> {
     None
 }
 
-#[derive(Copy, Clone, Zeroable, Hash, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Zeroable, Hash, PartialEq, Eq)]
 struct QuestData {
     quest_id: u32,
     complete: bool,
 }
+
+/// Offsets of the fields of an `Achievement` inside the memory read for each of them
+#[derive(Copy, Clone, Default)]
+struct AchievementLayout {
+    id: usize,
+    completed: usize,
+}
+
+impl AchievementLayout {
+    /// Decodes the memory read for an achievement
+    fn decode(&self, item: &[u8; ACHIEVEMENT_SIZE]) -> QuestData {
+        let int = |offset: usize| bytemuck::pod_read_unaligned::<u32>(&item[offset..offset + 4]);
+
+        QuestData {
+            quest_id: int(self.id),
+            complete: item[self.completed] != 0,
+        }
+    }
+}
+
+/// Reason why the quests of a quest list couldn't be read
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum QuestListError {
+    /// The list itself can't be read
+    Unreadable,
+    /// The list reports more entries than `MAX_QUEST_LIST_LEN`, which means it
+    /// isn't a quest list (anymore)
+    TooLong(usize),
+}
+
+/// Reads the quests contained in a quest list. An empty list is read as an empty `Vec`.
+fn read_quest_list<R: MemoryReader>(
+    reader: &R,
+    list: CSharpList<[u8; ACHIEVEMENT_SIZE]>,
+    layout: &AchievementLayout,
+) -> Result<Vec<QuestData>, QuestListError> {
+    let count = list.get_count(reader).ok_or(QuestListError::Unreadable)?;
+    if count > MAX_QUEST_LIST_LEN {
+        return Err(QuestListError::TooLong(count));
+    }
+
+    // The iterator skips unreadable entries, so it can't report its exact length.
+    // The capacity is reserved upfront to avoid reallocating while collecting.
+    let mut quests = Vec::with_capacity(count);
+    quests.extend(list.iter(reader).map(|item| layout.decode(&item)));
+    Ok(quests)
+}
+
+/// Set of quests present in a quest list, along with the completed ones, stored
+/// as bitsets for fast membership tests. Quest IDs are all lower than 64.
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+struct QuestSet {
+    present: u64,
+    completed: u64,
+}
+
+impl QuestSet {
+    fn new(list: &[QuestData]) -> Self {
+        list.iter().filter(|quest| quest.quest_id < u64::BITS).fold(
+            Self::default(),
+            |mut set, quest| {
+                set.present |= 1 << quest.quest_id;
+                if quest.complete {
+                    set.completed |= 1 << quest.quest_id;
+                }
+                set
+            },
+        )
+    }
+
+    /// Returns the number of quests in the set
+    const fn len(&self) -> u32 {
+        self.present.count_ones()
+    }
+
+    /// Returns the number of completed quests in the set
+    const fn completed_count(&self) -> u32 {
+        self.completed.count_ones()
+    }
+
+    /// Returns whether the set is not empty and all of its quests are complete
+    const fn all_quests_complete(&self) -> bool {
+        self.present != 0 && self.completed == self.present
+    }
+
+    /// Returns whether the specified quest is complete, or `None` if it's missing from the list
+    fn is_complete(&self, quest_id: u32) -> Option<bool> {
+        let mask = 1u64.checked_shl(quest_id)?;
+        match self.present & mask {
+            0 => None,
+            _ => Some(self.completed & mask != 0),
+        }
+    }
+
+    /// Returns whether all the specified quests are complete, or `None` if any
+    /// of them is missing from the list
+    fn all_complete(&self, quest_ids: &[u32]) -> Option<bool> {
+        quest_ids.iter().try_fold(true, |acc, &quest_id| {
+            Some(acc && self.is_complete(quest_id)?)
+        })
+    }
+}
+
+/// Maximum number of entries expected in a quest list. Any list reporting more
+/// entries than this is considered corrupted, eg. while being reloaded.
+const MAX_QUEST_LIST_LEN: usize = 128;
+
+/// Size of the memory read for each `Achievement` object
+const ACHIEVEMENT_SIZE: usize = 0x68;
+
+/// Names the completion flag of `Achievement` can be stored with, in order of
+/// preference. Auto-properties get a compiler-generated backing field instead.
+const ACHIEVEMENT_COMPLETED_FIELDS: &[&str] = &[
+    "_completed",
+    "completed",
+    "<Completed>k__BackingField",
+    "<completed>k__BackingField",
+    "<IsCompleted>k__BackingField",
+];
+
+/// IDs of the eight Find Chameleon quests
+const CHAMELEON_QUESTS: [u32; 8] = [36, 37, 38, 41, 42, 43, 44, 45];
+
+/// The in-game lists holding the quests the autosplitter keeps track of
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum QuestList {
+    /// Main quests, stored in `Journal.achievementMaster`
+    Main,
+    /// Cat-chievements, stored in `Journal.achievementSecondary`
+    Secondary,
+}
+
+/// A quest tracked by the autosplitter
+struct QuestEntry {
+    list: QuestList,
+    quest_id: u32,
+    /// In-game name of the quest
+    name: &'static str,
+    /// Whether splitting on the quest is enabled in the settings
+    setting: fn(&Settings) -> bool,
+}
+
+impl QuestEntry {
+    const fn new(
+        list: QuestList,
+        quest_id: u32,
+        name: &'static str,
+        setting: fn(&Settings) -> bool,
+    ) -> Self {
+        Self {
+            list,
+            quest_id,
+            name,
+            setting,
+        }
+    }
+
+    /// Returns whether each quest appears only once in the specified table
+    const fn all_unique(table: &[Self]) -> bool {
+        let mut i = 0;
+        while i < table.len() {
+            let mut j = i + 1;
+            while j < table.len() {
+                if table[i].list as u8 == table[j].list as u8
+                    && table[i].quest_id == table[j].quest_id
+                {
+                    return false;
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+        true
+    }
+}
+
+/// Single source of truth for the quests tracked by the autosplitter, mapping each
+/// quest to its name and to the setting enabling the split
+const QUESTS: &[QuestEntry] = &[
+    QuestEntry::new(QuestList::Main, 8, "Catch a bird", |s| s.catch_a_bird),
+    QuestEntry::new(QuestList::Main, 12, "Fetch the dog's balls", |s| {
+        s.fetch_dog_balls
+    }),
+    QuestEntry::new(QuestList::Main, 19, "Bring crow 25 shinies", |s| {
+        s.bring_crow_25_shinies
+    }),
+    QuestEntry::new(
+        QuestList::Main,
+        21,
+        "Rescue the tanuki from the pipe",
+        |s| s.rescue_tanuki,
+    ),
+    QuestEntry::new(
+        QuestList::Main,
+        24,
+        "Fetch 3 feathers for the tanuki",
+        |s| s.fetch_3_feathers,
+    ),
+    QuestEntry::new(QuestList::Main, 28, "Reunite the duckling family", |s| {
+        s.reunite_the_family
+    }),
+    QuestEntry::new(QuestList::Main, 29, "Help the Mayor get some sleep", |s| {
+        s.help_mayor
+    }),
+    QuestEntry::new(QuestList::Main, 32, "Find the crow", |s| s.find_crow),
+    QuestEntry::new(QuestList::Main, 34, "Become an artist", |s| s.become_artist),
+    QuestEntry::new(QuestList::Main, 36, "Find Chameleon", |s| {
+        s.find_chameleon_1
+    }),
+    QuestEntry::new(QuestList::Main, 37, "Find Chameleon... again!", |s| {
+        s.find_chameleon_2
+    }),
+    QuestEntry::new(QuestList::Main, 38, "Find Chameleon, part III", |s| {
+        s.find_chameleon_3
+    }),
+    QuestEntry::new(QuestList::Main, 39, "Waiting on a sunbeam", |s| s.sunbeam),
+    QuestEntry::new(QuestList::Main, 41, "Find Chameleon: Episode 4", |s| {
+        s.find_chameleon_4
+    }),
+    QuestEntry::new(QuestList::Main, 42, "Find Chameleon: 5IVE!", |s| {
+        s.find_chameleon_5
+    }),
+    QuestEntry::new(QuestList::Main, 43, "Chameleon 6: Find and Furious", |s| {
+        s.find_chameleon_6
+    }),
+    QuestEntry::new(QuestList::Main, 44, "Find Chameleon: Chapter 7", |s| {
+        s.find_chameleon_7
+    }),
+    QuestEntry::new(
+        QuestList::Main,
+        45,
+        "Find Chameleon: The Return of Chaml",
+        |s| s.find_chameleon_8,
+    ),
+    QuestEntry::new(QuestList::Main, 47, "Steal the gardener's lunch", |s| {
+        s.steal_lunch
+    }),
+    QuestEntry::new(QuestList::Main, 49, "Pose for Beetle", |s| {
+        s.pose_for_beetle
+    }),
+    QuestEntry::new(QuestList::Main, 56, "Boss Cat vs. Ramune!", |s| {
+        s.catch_yellow_bird
+    }),
+    QuestEntry::new(QuestList::Secondary, 1, "Hello Everyone!", |s| {
+        s.hello_everyone
+    }),
+    QuestEntry::new(QuestList::Secondary, 2, "Quack Troops!", |s| s.quack_troops),
+    QuestEntry::new(QuestList::Secondary, 3, "Snap Happy!", |s| s.snap_happy),
+    QuestEntry::new(QuestList::Secondary, 7, "Capped Crusader", |s| {
+        s.capped_crusader
+    }),
+    QuestEntry::new(QuestList::Secondary, 8, "World Traveler", |s| {
+        s.world_traveler
+    }),
+    QuestEntry::new(QuestList::Secondary, 9, "Cat Napper", |s| s.cat_napper),
+    QuestEntry::new(QuestList::Secondary, 10, "Bird Botherer", |s| {
+        s.bird_botherer
+    }),
+    QuestEntry::new(QuestList::Secondary, 11, "If I Fits, I Sits", |s| {
+        s.if_i_fits_i_sits
+    }),
+    QuestEntry::new(QuestList::Secondary, 12, "Litter Picker", |s| {
+        s.litter_picker
+    }),
+    QuestEntry::new(QuestList::Secondary, 13, "Smash Hit", |s| s.smash_hit),
+    QuestEntry::new(QuestList::Secondary, 14, "Sticky Business", |s| {
+        s.sticky_business
+    }),
+    QuestEntry::new(QuestList::Secondary, 15, "Give A Dog A Bone", |s| {
+        s.give_a_dog_a_bone
+    }),
+    QuestEntry::new(QuestList::Secondary, 16, "Cult of Purr-sonality", |s| {
+        s.cult_of_purrsonality
+    }),
+    QuestEntry::new(QuestList::Secondary, 17, "Local Celebrity", |s| {
+        s.local_celebrity
+    }),
+    QuestEntry::new(QuestList::Secondary, 19, "Papa-cat-zi", |s| s.papa_cat_zi),
+    QuestEntry::new(QuestList::Secondary, 23, "Cat-Like Reflexes", |s| {
+        s.cat_like_reflexes
+    }),
+    QuestEntry::new(QuestList::Secondary, 24, "Back Of The Net", |s| {
+        s.back_of_the_net
+    }),
+    QuestEntry::new(QuestList::Secondary, 26, "Surprise!", |s| s.surprise),
+    QuestEntry::new(QuestList::Secondary, 27, "Fruit Fall", |s| s.fruit_fall),
+    QuestEntry::new(QuestList::Secondary, 30, "Industrial Artist", |s| {
+        s.industrial_artist
+    }),
+    QuestEntry::new(QuestList::Secondary, 31, "Checkmate!", |s| s.checkmate),
+    QuestEntry::new(QuestList::Secondary, 32, "To Me, To You", |s| {
+        s.to_me_to_you
+    }),
+    QuestEntry::new(QuestList::Secondary, 33, "No Parking!", |s| s.no_parking),
+    QuestEntry::new(QuestList::Secondary, 34, "Rub-A-Dub-Dub!", |s| {
+        s.rub_a_dub_dub
+    }),
+    QuestEntry::new(QuestList::Secondary, 36, "And Stay Out!", |s| {
+        s.and_stay_out
+    }),
+    QuestEntry::new(QuestList::Secondary, 37, "Killer Kitty!", |s| {
+        s.killer_kitty
+    }),
+    QuestEntry::new(QuestList::Secondary, 38, "Who Needs Cash?", |s| {
+        s.who_needs_cash
+    }),
+    QuestEntry::new(QuestList::Secondary, 39, "Little Kitty, Big City", |s| {
+        s.little_kitty_big_city
+    }),
+    QuestEntry::new(QuestList::Secondary, 41, "Can't Stop The Feelings", |s| {
+        s.cant_stop_the_feelings
+    }),
+    QuestEntry::new(QuestList::Secondary, 42, "What Sweet Music", |s| {
+        s.what_sweet_music
+    }),
+    QuestEntry::new(QuestList::Secondary, 43, "Trip Hazard", |s| s.trip_hazard),
+    QuestEntry::new(QuestList::Secondary, 44, "Splish!", |s| s.splish),
+    QuestEntry::new(QuestList::Secondary, 45, "Decluttering", |s| s.decluttering),
+    QuestEntry::new(QuestList::Secondary, 46, "Dumpster Diving", |s| {
+        s.dumpster_diving
+    }),
+];
+
+const _: () = assert!(QuestEntry::all_unique(QUESTS), "Duplicate quest in QUESTS");
+
+impl QuestList {
+    fn entry(self, quest_id: u32) -> Option<&'static QuestEntry> {
+        QUESTS
+            .iter()
+            .find(|entry| entry.list == self && entry.quest_id == quest_id)
+    }
+
+    /// Returns whether splitting on the specified quest is enabled in the settings
+    fn is_enabled(self, settings: &Settings, quest_id: u32) -> bool {
+        self.entry(quest_id)
+            .is_some_and(|entry| (entry.setting)(settings))
+    }
+
+    /// Returns every quest enabled in the settings that got completed in the current
+    /// update, in the order of the list
+    fn newly_completed(self, settings: &Settings, quests: &Pair<Vec<QuestData>>) -> Vec<u32> {
+        // When the list becomes readable again after being empty (eg. while the Journal
+        // is being reloaded mid-run), the first populated update is only used as a
+        // baseline, so quests completed in the meantime don't trigger spurious splits.
+        if quests.old.is_empty() {
+            return Vec::new();
+        }
+
+        // The same ID might appear more than once in the list, so each quest is compared
+        // with the entry having the same ID and the same number of previous occurrences
+        quests
+            .current
+            .iter()
+            .enumerate()
+            .filter(|(_, quest)| quest.complete && self.is_enabled(settings, quest.quest_id))
+            .filter(|&(index, quest)| {
+                let occurrence = quests.current[..index]
+                    .iter()
+                    .filter(|other| other.quest_id == quest.quest_id)
+                    .count();
+
+                quests
+                    .old
+                    .iter()
+                    .filter(|old| old.quest_id == quest.quest_id)
+                    .nth(occurrence)
+                    .is_some_and(|old| !old.complete)
+            })
+            .map(|(_, quest)| quest.quest_id)
+            .collect()
+    }
+
+    /// Returns the IDs of all the quests tracked in the current list, along with
+    /// whether splitting on them is enabled in the settings
+    fn tracked_quests(self, settings: &Settings) -> impl Iterator<Item = (u32, bool)> + '_ {
+        QUESTS
+            .iter()
+            .filter(move |entry| entry.list == self)
+            .map(|entry| (entry.quest_id, (entry.setting)(settings)))
+    }
+
+    /// Returns the in-game name of the specified quest
+    fn name(self, quest_id: u32) -> Option<&'static str> {
+        self.entry(quest_id).map(|entry| entry.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ID of the Rescue the tanuki from the pipe quest
+    const RESCUE_TANUKI_QUEST: u32 = 21;
+
+    /// Returns the settings with everything disabled and every option set to its default
+    fn settings() -> Settings {
+        Settings {
+            general: Title,
+            autosplitter_enabled: false,
+            start: false,
+            start_requires_world_load: false,
+            reset_on_spawn: false,
+            debounce_start: false,
+            load_removal: Title,
+            pause_in_loading_scene: false,
+            pause_in_main_menu: false,
+            load_scenes_override: false,
+            pause_during_teleport: false,
+            split: Title,
+            eat_fish: false,
+            split_priority: SplitPriority::QuestsFirst,
+            split_only_first_quest: false,
+            min_run_seconds: MinRunDuration::Off,
+            quest_merge_window: QuestMergeWindow::Off,
+            got_home: false,
+            game_end_source: GameEndSource::OutroFlag,
+            debounce_end: false,
+            allow_end_retrigger: false,
+            split_on_100_percent: false,
+            split_on_quests_and_fish: false,
+            split_scene_level: false,
+            split_on_scene_exit: false,
+            split_on_teleport: false,
+            quests: Title,
+            find_crow: false,
+            bring_crow_25_shinies: false,
+            become_artist: false,
+            catch_a_bird: false,
+            help_mayor: false,
+            rescue_tanuki: false,
+            reunite_the_family: false,
+            fetch_3_feathers: false,
+            pose_for_beetle: false,
+            fetch_dog_balls: false,
+            find_chameleon_1: false,
+            find_chameleon_2: false,
+            find_chameleon_3: false,
+            find_chameleon_4: false,
+            find_chameleon_5: false,
+            find_chameleon_6: false,
+            find_chameleon_7: false,
+            find_chameleon_8: false,
+            split_on_all_chameleons: false,
+            end_on_last_chameleon: false,
+            steal_lunch: false,
+            catch_yellow_bird: false,
+            sunbeam: false,
+            catchievements: Title,
+            hello_everyone: false,
+            quack_troops: false,
+            snap_happy: false,
+            capped_crusader: false,
+            world_traveler: false,
+            cat_napper: false,
+            bird_botherer: false,
+            if_i_fits_i_sits: false,
+            litter_picker: false,
+            smash_hit: false,
+            sticky_business: false,
+            give_a_dog_a_bone: false,
+            cult_of_purrsonality: false,
+            local_celebrity: false,
+            papa_cat_zi: false,
+            cat_like_reflexes: false,
+            back_of_the_net: false,
+            surprise: false,
+            fruit_fall: false,
+            industrial_artist: false,
+            checkmate: false,
+            to_me_to_you: false,
+            no_parking: false,
+            rub_a_dub_dub: false,
+            and_stay_out: false,
+            killer_kitty: false,
+            who_needs_cash: false,
+            little_kitty_big_city: false,
+            cant_stop_the_feelings: false,
+            what_sweet_music: false,
+            trip_hazard: false,
+            splish: false,
+            decluttering: false,
+            dumpster_diving: false,
+            debug: Title,
+            log_splits: false,
+            log_quest_settings: false,
+            log_splits_template: false,
+            show_start_trigger_state: false,
+            dry_run: false,
+            log_quest_times: false,
+            log_pauses: false,
+            log_offsets: false,
+        }
+    }
+
+    /// Returns what most split tests start from: the settings of [`settings`], watchers
+    /// with nothing read yet and no run in progress
+    fn setup() -> (Settings, Watchers, RunState) {
+        (settings(), Watchers::default(), RunState::default())
+    }
+
+    fn quest(quest_id: u32, complete: bool) -> QuestData {
+        QuestData { quest_id, complete }
+    }
+
+    /// Feeds a new state of the main quest list to the watchers, as if it was read in a new update
+    fn update_quests(watchers: &mut Watchers, quests: &[QuestData]) {
+        watchers.quest_list.update_infallible(quests.to_vec());
+        watchers.quest_set.update_infallible(QuestSet::new(quests));
+    }
+
+    /// Same as `update_quests`, for the cat-chievements
+    fn update_catchievements(watchers: &mut Watchers, quests: &[QuestData]) {
+        watchers
+            .quest_secondary_list
+            .update_infallible(quests.to_vec());
+        watchers
+            .quest_secondary_set
+            .update_infallible(QuestSet::new(quests));
+    }
+
+    #[test]
+    fn quest_set_tracks_the_quests_of_the_list() {
+        let set = QuestSet::new(&[quest(3, true), quest(5, false), quest(64, true)]);
+
+        assert_eq!(set.is_complete(3), Some(true));
+        assert_eq!(set.is_complete(5), Some(false));
+        assert_eq!(set.is_complete(4), None);
+        // IDs that don't fit in the bitsets are left out
+        assert_eq!(set.is_complete(64), None);
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.completed_count(), 1);
+        assert!(!set.all_quests_complete());
+
+        assert_eq!(set.all_complete(&[3]), Some(true));
+        assert_eq!(set.all_complete(&[3, 5]), Some(false));
+        assert_eq!(set.all_complete(&[3, 4]), None);
+
+        assert!(QuestSet::new(&[quest(3, true)]).all_quests_complete());
+        assert!(!QuestSet::default().all_quests_complete());
+    }
+
+    #[test]
+    fn quests_table_fits_in_a_quest_set() {
+        assert!(QuestEntry::all_unique(QUESTS));
+        assert!(QUESTS.iter().all(|entry| entry.quest_id < u64::BITS));
+
+        let duplicated = [
+            QuestEntry::new(QuestList::Main, 8, "", |_| true),
+            QuestEntry::new(QuestList::Main, 12, "", |_| true),
+            QuestEntry::new(QuestList::Main, 8, "", |_| true),
+        ];
+        assert!(!QuestEntry::all_unique(&duplicated));
+        // The same ID in the other list is a different quest
+        let other_list = [
+            QuestEntry::new(QuestList::Main, 8, "", |_| true),
+            QuestEntry::new(QuestList::Secondary, 8, "", |_| true),
+        ];
+        assert!(QuestEntry::all_unique(&other_list));
+    }
+
+    #[test]
+    fn newly_completed_compares_duplicated_quests_by_occurrence() {
+        let mut settings = settings();
+        settings.rescue_tanuki = true;
+        let newly_completed = |old: &[QuestData], current: &[QuestData]| {
+            QuestList::Main.newly_completed(
+                &settings,
+                &Pair {
+                    old: old.to_vec(),
+                    current: current.to_vec(),
+                },
+            )
+        };
+
+        let id = RESCUE_TANUKI_QUEST;
+        // The second occurrence got completed
+        assert_eq!(
+            newly_completed(
+                &[quest(id, true), quest(id, false)],
+                &[quest(id, true), quest(id, true)]
+            ),
+            [id]
+        );
+        // The first occurrence was already complete, so nothing changed
+        assert_eq!(
+            newly_completed(
+                &[quest(id, true), quest(id, false)],
+                &[quest(id, true), quest(id, false)]
+            ),
+            []
+        );
+        // A new occurrence has nothing to compare with
+        assert_eq!(
+            newly_completed(&[quest(id, true)], &[quest(id, true), quest(id, true)]),
+            []
+        );
+        // The first update after the list was empty is only a baseline
+        assert_eq!(newly_completed(&[], &[quest(id, true)]), []);
+        // Quests not enabled in the settings never count
+        assert_eq!(newly_completed(&[quest(8, false)], &[quest(8, true)]), []);
+    }
+
+    #[test]
+    fn quests_completed_together_split_separately() {
+        let (mut settings, mut watchers, mut state) = setup();
+        settings.eat_fish = true;
+        settings.rescue_tanuki = true;
+        settings.catch_a_bird = true;
+
+        watchers.is_post_eating.update_infallible(false);
+        update_quests(
+            &mut watchers,
+            &[quest(8, false), quest(RESCUE_TANUKI_QUEST, false)],
+        );
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+
+        // Two quests and a fish in the same update
+        watchers.is_post_eating.update_infallible(true);
+        update_quests(
+            &mut watchers,
+            &[quest(8, true), quest(RESCUE_TANUKI_QUEST, true)],
+        );
+        let mut splits = vec![split(&watchers, &settings, &mut state)];
+
+        watchers.is_post_eating.update_infallible(true);
+        update_quests(
+            &mut watchers,
+            &[quest(8, true), quest(RESCUE_TANUKI_QUEST, true)],
+        );
+        splits.extend((0..3).map(|_| split(&watchers, &settings, &mut state)));
+        assert_eq!(
+            splits,
+            [
+                Some(SplitEvent::Quest(QuestList::Main, 8)),
+                Some(SplitEvent::Quest(QuestList::Main, RESCUE_TANUKI_QUEST)),
+                Some(SplitEvent::Other("fish eaten")),
+                None,
+            ]
+        );
+        assert_eq!(
+            state.last_completed_quest,
+            Some((QuestList::Main, RESCUE_TANUKI_QUEST))
+        );
+    }
+
+    #[test]
+    fn split_priority_puts_the_fish_first_but_never_after_the_game_end() {
+        let (mut settings, mut watchers, mut state) = setup();
+        settings.eat_fish = true;
+        settings.rescue_tanuki = true;
+        settings.got_home = true;
+        settings.split_priority = SplitPriority::FishFirst;
+
+        watchers.is_post_eating.update_infallible(false);
+        watchers.end_trigger.update_infallible(false);
+        update_quests(&mut watchers, &[quest(RESCUE_TANUKI_QUEST, false)]);
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+
+        watchers.is_post_eating.update_infallible(true);
+        watchers.end_trigger.update_infallible(true);
+        update_quests(&mut watchers, &[quest(RESCUE_TANUKI_QUEST, true)]);
+        let mut splits = vec![split(&watchers, &settings, &mut state)];
+
+        watchers.is_post_eating.update_infallible(true);
+        watchers.end_trigger.update_infallible(true);
+        update_quests(&mut watchers, &[quest(RESCUE_TANUKI_QUEST, true)]);
+        splits.extend((0..3).map(|_| split(&watchers, &settings, &mut state)));
+        assert_eq!(
+            splits,
+            [
+                Some(SplitEvent::Other("fish eaten")),
+                Some(SplitEvent::Quest(QuestList::Main, RESCUE_TANUKI_QUEST)),
+                Some(SplitEvent::Other("game end (outro flag)")),
+                None,
+            ]
+        );
+    }
+
+    /// Feeds the result of a read of the main quest list to the watchers, the same
+    /// way the lists read from the game are
+    fn read_quests(watchers: &mut Watchers, quests: Option<&[QuestData]>) {
+        update_quest_list(&mut watchers.quest_list, quests.map(<[_]>::to_vec));
+        update_quest_set(&mut watchers.quest_set, &watchers.quest_list);
+    }
+
+    #[test]
+    fn simultaneous_splits_fire_one_per_tick() {
+        let (mut settings, mut watchers, mut state) = setup();
+        settings.eat_fish = true;
+        settings.rescue_tanuki = true;
+
+        watchers.is_post_eating.update_infallible(false);
+        update_quests(&mut watchers, &[quest(RESCUE_TANUKI_QUEST, false)]);
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+
+        // The quest comes first, then the fish on the following tick
+        watchers.is_post_eating.update_infallible(true);
+        update_quests(&mut watchers, &[quest(RESCUE_TANUKI_QUEST, true)]);
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Quest(QuestList::Main, RESCUE_TANUKI_QUEST))
+        );
+
+        watchers.is_post_eating.update_infallible(true);
+        update_quests(&mut watchers, &[quest(RESCUE_TANUKI_QUEST, true)]);
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Other("fish eaten"))
+        );
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+    }
+
+    #[test]
+    fn simultaneous_events_split_separately() {
+        let (mut settings, mut watchers, mut state) = setup();
+        settings.split_on_teleport = true;
+        settings.split_scene_level = true;
+
+        watchers.is_teleporting.update_infallible(false);
+        watchers.split_scene.update_infallible(None);
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+
+        // Teleporting into the level
+        watchers.is_teleporting.update_infallible(true);
+        watchers.split_scene.update_infallible(Some(0));
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Other("scene change"))
+        );
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Other("portal used"))
+        );
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+    }
+
+    #[test]
+    fn game_end_is_fired_last() {
+        let (mut settings, mut watchers, mut state) = setup();
+        settings.eat_fish = true;
+        settings.got_home = true;
+
+        watchers.is_post_eating.update_infallible(false);
+        watchers.end_trigger.update_infallible(false);
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+
+        watchers.is_post_eating.update_infallible(true);
+        watchers.end_trigger.update_infallible(true);
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Other("fish eaten"))
+        );
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Other("game end (outro flag)"))
+        );
+    }
+
+    #[test]
+    fn game_end_splits_again_after_a_reset() {
+        let (mut settings, mut watchers, mut state) = setup();
+        settings.got_home = true;
+
+        watchers.end_trigger.update_infallible(false);
+        watchers.end_trigger.update_infallible(true);
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Other("game end (outro flag)"))
+        );
+
+        watchers.end_trigger.update_infallible(false);
+        watchers.end_trigger.update_infallible(true);
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+
+        // A reset of the timer, either by the runner or by the autosplitter
+        state.reset();
+        watchers.end_trigger.update_infallible(false);
+        watchers.end_trigger.update_infallible(true);
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Other("game end (outro flag)"))
+        );
+    }
+
+    #[test]
+    fn min_run_duration_is_counted_from_the_last_reset() {
+        let mut settings = settings();
+        settings.eat_fish = true;
+        settings.min_run_seconds = MinRunDuration::Seconds5;
+
+        let mut watchers = Watchers::default();
+        let mut state = RunState {
+            game_time: Duration::seconds(60),
+            ..RunState::default()
+        };
+        state.reset();
+        assert_eq!(state.game_time, Duration::ZERO);
+
+        watchers.is_post_eating.update_infallible(false);
+        watchers.is_post_eating.update_infallible(true);
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+
+        state.game_time = Duration::seconds(5);
+        watchers.is_post_eating.update_infallible(false);
+        watchers.is_post_eating.update_infallible(true);
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Other("fish eaten"))
+        );
+    }
+
+    #[test]
+    fn queued_splits_are_fired_in_non_gameplay_scenes() {
+        let (mut settings, mut watchers, mut state) = setup();
+        settings.eat_fish = true;
+        settings.split_on_teleport = true;
+
+        watchers.is_post_eating.update_infallible(false);
+        watchers.is_post_eating.update_infallible(true);
+        watchers.is_teleporting.update_infallible(false);
+        watchers.is_teleporting.update_infallible(true);
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Other("fish eaten"))
+        );
+
+        // Nothing new triggers in the main menu, but the queued split still fires
+        // and the cooldowns keep running
+        watchers
+            .scene
+            .update_infallible(scene_path(MAIN_MENU_SCENE));
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Other("portal used"))
+        );
+        for _ in 1..EVENT_COOLDOWN_TICKS {
+            assert_eq!(split(&watchers, &settings, &mut state), None);
+        }
+
+        watchers.scene.update_infallible(scene_path(b"Level_X"));
+        watchers.is_post_eating.update_infallible(true);
+        watchers.is_teleporting.update_infallible(false);
+        watchers.is_teleporting.update_infallible(true);
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Other("portal used"))
+        );
+    }
+
+    #[test]
+    fn got_home_cat_chievement_only_splits_as_the_game_end() {
+        let (mut settings, mut watchers, mut state) = setup();
+        settings.got_home = true;
+        settings.game_end_source = GameEndSource::GotHomeAchievement;
+        settings.little_kitty_big_city = true;
+
+        update_catchievements(&mut watchers, &[quest(GOT_HOME_ACHIEVEMENT, false)]);
+        update_catchievements(&mut watchers, &[quest(GOT_HOME_ACHIEVEMENT, true)]);
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Other("game end (got home cat-chievement)"))
+        );
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+    }
+
+    #[test]
+    fn last_chameleon_only_splits_as_the_game_end() {
+        let (mut settings, mut watchers, mut state) = setup();
+        settings.end_on_last_chameleon = true;
+        settings.find_chameleon_8 = true;
+
+        let chameleons = |found: usize| {
+            CHAMELEON_QUESTS
+                .iter()
+                .enumerate()
+                .map(|(index, &quest_id)| quest(quest_id, index < found))
+                .collect::<Vec<_>>()
+        };
+        update_quests(&mut watchers, &chameleons(7));
+        update_quests(&mut watchers, &chameleons(8));
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Other("game end (all chameleons found)"))
+        );
+
+        update_quests(&mut watchers, &chameleons(8));
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+    }
+
+    #[test]
+    fn spawn_radius() {
+        let spawn = [10.0, 1.0, -5.0];
+        assert!(is_near_spawn(spawn, spawn));
+        assert!(is_near_spawn(spawn, [11.0, 1.0, -4.0]));
+        assert!(!is_near_spawn(spawn, [10.0, 1.0, -5.0 + SPAWN_RADIUS]));
+        assert!(!is_near_spawn(spawn, [-10.0, 1.0, 5.0]));
+    }
+
+    #[test]
+    fn reset_requires_a_respawn() {
+        let mut settings = settings();
+        settings.reset_on_spawn = true;
+
+        let spawn = [10.0, 1.0, -5.0];
+        let mut watchers = Watchers::default();
+        let mut state = RunState {
+            game_time: SPAWN_RESET_GRACE,
+            ..RunState::default()
+        };
+
+        // Only runs started by the autosplitter know the spawn point
+        watchers.cat_position.update_infallible([50.0, 1.0, 50.0]);
+        watchers.cat_position.update_infallible(spawn);
+        assert!(!reset(&watchers, &settings, &state));
+
+        // Walking back to the spawn point
+        state.spawn_position = Some(spawn);
+        watchers.cat_position.update_infallible([12.5, 1.0, -5.0]);
+        watchers.cat_position.update_infallible([11.5, 1.0, -5.0]);
+        assert!(!reset(&watchers, &settings, &state));
+
+        // Going through a portal that leads next to the spawn point
+        watchers.is_teleporting.update_infallible(true);
+        watchers.cat_position.update_infallible([50.0, 1.0, 50.0]);
+        watchers.cat_position.update_infallible(spawn);
+        assert!(!reset(&watchers, &settings, &state));
+
+        watchers.is_teleporting.update_infallible(false);
+        watchers.is_teleporting.update_infallible(false);
+        watchers.cat_position.update_infallible([50.0, 1.0, 50.0]);
+        watchers.cat_position.update_infallible(spawn);
+        assert!(reset(&watchers, &settings, &state));
+
+        // Not before the grace window is over
+        state.game_time = Duration::ZERO;
+        assert!(!reset(&watchers, &settings, &state));
+    }
+
+    #[test]
+    fn disabled_load_scene_only_overrides_other_loads_when_asked() {
+        let mut settings = settings();
+        settings.pause_in_main_menu = false;
+
+        let main_menu = LOAD_SCENES
+            .iter()
+            .position(|&(name, _)| name == MAIN_MENU_SCENE);
+        let mut watchers = Watchers::default();
+        watchers.load_scene.update_infallible(main_menu);
+        watchers.is_loading_save.update_infallible(false);
+        assert_eq!(is_loading(&watchers, &settings), Some(false));
+
+        // Loading a save from the main menu
+        watchers.is_loading_save.update_infallible(true);
+        assert_eq!(is_loading(&watchers, &settings), Some(true));
+
+        settings.load_scenes_override = true;
+        assert_eq!(is_loading(&watchers, &settings), Some(false));
+
+        settings.pause_in_main_menu = true;
+        watchers.is_loading_save.update_infallible(false);
+        assert_eq!(is_loading(&watchers, &settings), Some(true));
+    }
+
+    #[test]
+    fn splits_template_matches_the_splits() {
+        let mut settings = settings();
+        settings.rescue_tanuki = true;
+        settings.got_home = true;
+        settings.game_end_source = GameEndSource::GotHomeAchievement;
+        settings.little_kitty_big_city = true;
+
+        // The got home cat-chievement never splits
+        assert_eq!(
+            splits_template(&settings),
+            ["Rescue the tanuki from the pipe", "Game end"]
+        );
+
+        // The last chameleon found is the game end
+        settings.got_home = false;
+        settings.end_on_last_chameleon = true;
+        settings.find_chameleon_1 = true;
+        settings.find_chameleon_2 = true;
+        settings.find_chameleon_3 = true;
+        settings.find_chameleon_4 = true;
+        settings.find_chameleon_5 = true;
+        settings.find_chameleon_6 = true;
+        settings.find_chameleon_7 = true;
+        settings.find_chameleon_8 = true;
+        let template = splits_template(&settings);
+        assert_eq!(
+            template
+                .iter()
+                .filter(|&&name| name == "Chameleon found")
+                .count(),
+            CHAMELEON_QUESTS.len() - 1
+        );
+    }
+
+    /// Memory of a fake game, made of separate regions of canned bytes
+    #[derive(Default)]
+    struct FakeMemory {
+        regions: Vec<(u64, Vec<u8>)>,
+    }
+
+    impl FakeMemory {
+        /// Maps the bytes at the specified address, replacing the region previously
+        /// mapped there
+        fn write(&mut self, address: u64, bytes: &[u8]) {
+            self.regions.retain(|&(start, _)| start != address);
+            self.regions.push((address, bytes.to_vec()));
+        }
+
+        /// Maps a C# `List` of references at the specified address, with its backing
+        /// array right after it
+        fn write_list(&mut self, address: u64, elements: &[u64]) {
+            let items = address + 0x100;
+            let mut list = vec![0; 0x20];
+            list[0x10..0x18].copy_from_slice(&items.to_le_bytes());
+            list[0x18..0x1C].copy_from_slice(&(elements.len() as u32).to_le_bytes());
+            self.write(address, &list);
+
+            let mut array = vec![0; 0x20];
+            for element in elements {
+                array.extend_from_slice(&element.to_le_bytes());
+            }
+            self.write(items, &array);
+        }
+    }
+
+    impl MemoryReader for FakeMemory {
+        fn read_into_buf(&self, address: impl Into<asr::Address>, buf: &mut [u8]) -> Option<()> {
+            let address = address.into().value();
+            let (start, bytes) = self
+                .regions
+                .iter()
+                .find(|(start, bytes)| (*start..*start + bytes.len() as u64).contains(&address))?;
+            let offset = (address - start) as usize;
+            buf.copy_from_slice(bytes.get(offset..offset + buf.len())?);
+            Some(())
+        }
+    }
+
+    /// Returns the fields of a `CatPlayer` whose flags are at the offsets 0x20 and 0x21
+    fn cat_player() -> CatPlayer {
+        CatPlayer {
+            instance: UnityPointer::new("CatPlayer", 0, &["_instance"]),
+            block_start: 0x20,
+            block: RefCell::new(vec![0; 2]),
+            is_post_eating: 0x20,
+            is_teleporting: 0x21,
+        }
+    }
+
+    /// Returns the path of the scene with the specified name
+    fn scene_path(name: &[u8]) -> ArrayCString<SCENE_PATH_LEN> {
+        let path = [b"Assets/Scenes/", name, b".unity"].concat();
+        let mut bytes = [0; SCENE_PATH_LEN];
+        bytes[..path.len()].copy_from_slice(&path);
+        bytemuck::cast(bytes)
+    }
+
+    /// State of the fake game in a single update
+    #[derive(Copy, Clone)]
+    struct Snapshot {
+        scene: &'static [u8],
+        allow_player_shake: bool,
+        is_outro: bool,
+        /// Quests of the main quest list, with their completion
+        quests: &'static [(u32, bool)],
+        cat_position: [f32; 3],
+    }
+
+    impl Snapshot {
+        const MENU: Self = Self {
+            scene: MAIN_MENU_SCENE,
+            allow_player_shake: false,
+            is_outro: false,
+            quests: &[(RESCUE_TANUKI_QUEST, false)],
+            cat_position: [0.0; 3],
+        };
+    }
+
+    /// Addresses of the fake game
+    const JOURNAL_MASTER: u64 = 0x1000;
+    const QUEST_LIST: u64 = 0x2000;
+    const ACHIEVEMENTS: u64 = 0x3000;
+    const CAT_PLAYER: u64 = 0x4000;
+    const FLAGS: u64 = 0x5000;
+
+    const ACHIEVEMENT_LAYOUT: AchievementLayout = AchievementLayout {
+        id: 0x10,
+        completed: 0x14,
+    };
+
+    /// Maps the snapshot in the memory of the fake game
+    fn write_snapshot(memory: &mut FakeMemory, snapshot: &Snapshot) {
+        memory.write(JOURNAL_MASTER, &QUEST_LIST.to_le_bytes());
+
+        let achievements: Vec<u64> = (0..snapshot.quests.len() as u64)
+            .map(|index| ACHIEVEMENTS + index * 0x100)
+            .collect();
+        memory.write_list(QUEST_LIST, &achievements);
+        for (&address, &(quest_id, complete)) in achievements.iter().zip(snapshot.quests) {
+            let mut achievement = [0; ACHIEVEMENT_SIZE];
+            achievement[0x10..0x14].copy_from_slice(&quest_id.to_le_bytes());
+            achievement[0x14] = complete as u8;
+            memory.write(address, &achievement);
+        }
+
+        // The cat is never teleporting
+        memory.write(CAT_PLAYER, &[0; 0x10]);
+        memory.write(
+            FLAGS,
+            &[snapshot.allow_player_shake as u8, snapshot.is_outro as u8],
+        );
+    }
+
+    /// Reads the snapshot back from the memory of the fake game, the same way the
+    /// values get read from the game past the resolved pointers
+    fn read_snapshot(memory: &FakeMemory, snapshot: &Snapshot) -> Readings {
+        let flag = |offset: u64| {
+            memory
+                .read::<u8>(asr::Address::new(FLAGS + offset))
+                .map(|val| val != 0)
+        };
+        let quest_list = memory
+            .read::<CSharpList<[u8; ACHIEVEMENT_SIZE]>>(asr::Address::new(JOURNAL_MASTER))
+            .and_then(|list| read_quest_list(memory, list, &ACHIEVEMENT_LAYOUT).ok());
+
+        Readings {
+            scene: Some(scene_path(snapshot.scene)),
+            start_flag_available: true,
+            cat_player: cat_player().read_at(memory, Address64::new(CAT_PLAYER - 0x20)),
+            allow_player_shake: flag(0),
+            trashcan_found: true,
+            is_outro: flag(1).unwrap_or_default(),
+            quest_list,
+            quest_secondary_list: Some(Vec::new()),
+            cat_position: Some(snapshot.cat_position),
+            cat_position_used: true,
+            ..Default::default()
+        }
+    }
+
+    /// Replays the snapshots as consecutive updates, starting with the timer not
+    /// running, and returns the action taken in each of them
+    fn replay(settings: &Settings, snapshots: &[Snapshot]) -> Vec<Option<Action>> {
+        let mut memory = FakeMemory::default();
+        let mut watchers = Watchers::default();
+        let mut state = RunState::default();
+        let mut timer_state = TimerState::NotRunning;
+
+        snapshots
+            .iter()
+            .map(|snapshot| {
+                write_snapshot(&mut memory, snapshot);
+                watchers.update(read_snapshot(&memory, snapshot));
+                track_run_state(&watchers, &mut state, timer_state);
+
+                // The respawn is only allowed once the run has been going for a while
+                if timer_state == TimerState::Running {
+                    state.game_time += Duration::seconds(2);
+                }
+
+                let action = next_action(&watchers, settings, &mut state, timer_state);
+                match action {
+                    Some(Action::Start) => timer_state = TimerState::Running,
+                    Some(Action::Reset) => timer_state = TimerState::NotRunning,
+                    _ => {}
+                }
+                action
+            })
+            .collect()
+    }
+
+    #[test]
+    fn replay_of_a_full_run() {
+        let mut settings = settings();
+        settings.autosplitter_enabled = true;
+        settings.start = true;
+        settings.start_requires_world_load = true;
+        settings.rescue_tanuki = true;
+        settings.got_home = true;
+
+        let menu = Snapshot::MENU;
+        let loading = Snapshot {
+            scene: LOADING_SCENE,
+            ..menu
+        };
+        let level = Snapshot {
+            scene: b"Level_X",
+            ..menu
+        };
+        let gameplay = Snapshot {
+            allow_player_shake: true,
+            ..level
+        };
+        let quest_complete = Snapshot {
+            quests: &[(RESCUE_TANUKI_QUEST, true)],
+            ..gameplay
+        };
+        let outro = Snapshot {
+            is_outro: true,
+            ..quest_complete
+        };
+
+        assert_eq!(
+            replay(
+                &settings,
+                &[
+                    menu,
+                    loading,
+                    level,
+                    gameplay,
+                    gameplay,
+                    quest_complete,
+                    outro,
+                    outro
+                ]
+            ),
+            [
+                None,
+                None,
+                None,
+                Some(Action::Start),
+                None,
+                Some(Action::Split(SplitEvent::Quest(
+                    QuestList::Main,
+                    RESCUE_TANUKI_QUEST
+                ))),
+                Some(Action::Split(SplitEvent::Other("game end (outro flag)"))),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn replay_of_a_respawn() {
+        let mut settings = settings();
+        settings.autosplitter_enabled = true;
+        settings.start = true;
+        settings.reset_on_spawn = true;
+
+        let level = Snapshot {
+            scene: b"Level_X",
+            ..Snapshot::MENU
+        };
+        let spawn = Snapshot {
+            allow_player_shake: true,
+            ..level
+        };
+        let away = Snapshot {
+            cat_position: [50.0, 0.0, 0.0],
+            ..spawn
+        };
+        // The trash can gets shaken again right after the respawn
+        let respawn = Snapshot {
+            allow_player_shake: false,
+            ..spawn
+        };
+
+        // Walking away and back doesn't count before the grace period, and the
+        // start is held back right after the reset
+        assert_eq!(
+            replay(
+                &settings,
+                &[level, spawn, away, spawn, away, away, spawn, respawn, spawn, spawn]
+            ),
+            [
+                None,
+                Some(Action::Start),
+                None,
+                None,
+                None,
+                None,
+                Some(Action::Reset),
+                None,
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn photo_cat_chievements_dont_cross_trigger() {
+        // Local Celebrity (17) doesn't split for Papa-cat-zi (19), and the other way around
+        let mut settings = settings();
+        settings.papa_cat_zi = true;
+
+        let mut watchers = Watchers::default();
+        let mut state = RunState::default();
+        update_catchievements(&mut watchers, &[quest(17, false), quest(19, false)]);
+        update_catchievements(&mut watchers, &[quest(17, true), quest(19, false)]);
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+
+        update_catchievements(&mut watchers, &[quest(17, true), quest(19, true)]);
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Quest(QuestList::Secondary, 19))
+        );
+
+        settings.papa_cat_zi = false;
+        settings.local_celebrity = true;
+        let mut watchers = Watchers::default();
+        let mut state = RunState::default();
+        update_catchievements(&mut watchers, &[quest(17, false), quest(19, false)]);
+        update_catchievements(&mut watchers, &[quest(17, false), quest(19, true)]);
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+    }
+
+    #[test]
+    fn quest_list_repopulated_mid_run_is_a_baseline() {
+        let (mut settings, mut watchers, mut state) = setup();
+        settings.rescue_tanuki = true;
+        settings.catch_a_bird = true;
+
+        read_quests(
+            &mut watchers,
+            Some(&[quest(RESCUE_TANUKI_QUEST, false), quest(8, false)]),
+        );
+        read_quests(
+            &mut watchers,
+            Some(&[quest(RESCUE_TANUKI_QUEST, false), quest(8, false)]),
+        );
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+
+        // The Journal gets reloaded, and the quest completed in the meantime is
+        // already complete once the list comes back
+        read_quests(&mut watchers, Some(&[]));
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+        read_quests(
+            &mut watchers,
+            Some(&[quest(RESCUE_TANUKI_QUEST, true), quest(8, false)]),
+        );
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+
+        // The quests completed afterwards split as usual
+        read_quests(
+            &mut watchers,
+            Some(&[quest(RESCUE_TANUKI_QUEST, true), quest(8, true)]),
+        );
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Quest(QuestList::Main, 8))
+        );
+    }
+
+    #[test]
+    fn quest_list_tells_empty_lists_from_read_failures() {
+        let mut memory = FakeMemory::default();
+        let list = |memory: &FakeMemory| {
+            memory
+                .read::<CSharpList<[u8; ACHIEVEMENT_SIZE]>>(asr::Address::new(JOURNAL_MASTER))
+                .unwrap()
+        };
+        memory.write(JOURNAL_MASTER, &QUEST_LIST.to_le_bytes());
+
+        assert_eq!(
+            read_quest_list(&memory, list(&memory), &ACHIEVEMENT_LAYOUT),
+            Err(QuestListError::Unreadable)
+        );
+
+        memory.write_list(QUEST_LIST, &[]);
+        assert_eq!(
+            read_quest_list(&memory, list(&memory), &ACHIEVEMENT_LAYOUT),
+            Ok(Vec::new())
+        );
+
+        let mut achievement = [0; ACHIEVEMENT_SIZE];
+        achievement[0x10..0x14].copy_from_slice(&RESCUE_TANUKI_QUEST.to_le_bytes());
+        achievement[0x14] = 1;
+        memory.write(ACHIEVEMENTS, &achievement);
+        memory.write_list(QUEST_LIST, &[ACHIEVEMENTS]);
+        assert_eq!(
+            read_quest_list(&memory, list(&memory), &ACHIEVEMENT_LAYOUT),
+            Ok(vec![quest(RESCUE_TANUKI_QUEST, true)])
+        );
+
+        let achievements = [ACHIEVEMENTS; MAX_QUEST_LIST_LEN + 1];
+        memory.write_list(QUEST_LIST, &achievements);
+        assert_eq!(
+            read_quest_list(&memory, list(&memory), &ACHIEVEMENT_LAYOUT),
+            Err(QuestListError::TooLong(MAX_QUEST_LIST_LEN + 1))
+        );
+    }
+
+    #[test]
+    fn quest_list_read_failure_keeps_the_last_list() {
+        let (mut settings, mut watchers, mut state) = setup();
+        settings.rescue_tanuki = true;
+
+        read_quests(&mut watchers, Some(&[quest(RESCUE_TANUKI_QUEST, false)]));
+        read_quests(&mut watchers, None);
+        assert_eq!(split(&watchers, &settings, &mut state), None);
+
+        // Unlike an empty list, the failed read doesn't make the next one a baseline
+        read_quests(&mut watchers, Some(&[quest(RESCUE_TANUKI_QUEST, true)]));
+        assert_eq!(
+            split(&watchers, &settings, &mut state),
+            Some(SplitEvent::Quest(QuestList::Main, RESCUE_TANUKI_QUEST))
+        );
+    }
+
+    #[test]
+    fn start_is_held_back_right_after_a_reset() {
+        let mut settings = settings();
+        settings.autosplitter_enabled = true;
+        settings.start = true;
+        settings.reset_on_spawn = true;
+
+        let mut watchers = Watchers::default();
+        let mut state = RunState {
+            spawn_position: Some([0.0; 3]),
+            game_time: Duration::seconds(20),
+            ..Default::default()
+        };
+        watchers.cat_position.update_infallible([50.0, 0.0, 0.0]);
+        watchers.cat_position.update_infallible([0.0; 3]);
+        watchers.start_trigger.update_infallible(false);
+        watchers.start_trigger.update_infallible(true);
+        assert_eq!(
+            next_action(&watchers, &settings, &mut state, TimerState::Running),
+            Some(Action::Reset)
+        );
+
+        // The start conditions keep being met on every tick after the reset
+        for tick in 1..=RESET_START_COOLDOWN_TICKS {
+            track_run_state(&watchers, &mut state, TimerState::NotRunning);
+            watchers.start_trigger.update_infallible(false);
+            watchers.start_trigger.update_infallible(true);
+            assert_eq!(
+                next_action(&watchers, &settings, &mut state, TimerState::NotRunning),
+                (tick == RESET_START_COOLDOWN_TICKS).then_some(Action::Start)
+            );
+        }
+    }
+
+    #[test]
+    fn cooldown_lets_one_trigger_through() {
+        let mut cooldown = Cooldown::default();
+        assert!(!cooldown.check(false));
+        assert!(cooldown.check(true));
+
+        // Every tick of the cooldown is dropped, triggered or not
+        for _ in 0..EVENT_COOLDOWN_TICKS {
+            assert!(!cooldown.check(true));
+        }
+        assert!(cooldown.check(true));
+    }
+}
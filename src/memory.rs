@@ -0,0 +1,54 @@
+use alloc::{vec, vec::Vec};
+use asr::{Address, Process};
+
+use bytemuck::{AnyBitPattern, CheckedBitPattern};
+
+/// Source of the game's memory.
+///
+/// Everything decoding the game's data past the resolved pointers reads through
+/// this trait instead of [`Process`] directly, so the same logic can run on canned
+/// memory contents.
+pub trait MemoryReader {
+    /// Fills the buffer with the memory starting at the specified address
+    fn read_into_buf(&self, address: impl Into<Address>, buf: &mut [u8]) -> Option<()>;
+
+    /// Reads a value of the specified type at the specified address
+    fn read<T: CheckedBitPattern>(&self, address: impl Into<Address>) -> Option<T> {
+        let mut buf = vec![0; size_of::<T>()];
+        self.read_into_buf(address, &mut buf)?;
+        bytemuck::checked::try_pod_read_unaligned(&buf).ok()
+    }
+
+    /// Reads `len` consecutive values of the specified type, starting at the specified address
+    fn read_vec<T: AnyBitPattern>(
+        &self,
+        address: impl Into<Address>,
+        len: usize,
+    ) -> Option<Vec<T>> {
+        let mut buf = vec![0; len.checked_mul(size_of::<T>())?];
+        self.read_into_buf(address, &mut buf)?;
+        Some(
+            buf.chunks_exact(size_of::<T>())
+                .map(bytemuck::pod_read_unaligned)
+                .collect(),
+        )
+    }
+}
+
+impl MemoryReader for Process {
+    fn read_into_buf(&self, address: impl Into<Address>, buf: &mut [u8]) -> Option<()> {
+        Process::read_into_buf(self, address, buf).ok()
+    }
+
+    fn read<T: CheckedBitPattern>(&self, address: impl Into<Address>) -> Option<T> {
+        Process::read(self, address).ok()
+    }
+
+    fn read_vec<T: AnyBitPattern>(
+        &self,
+        address: impl Into<Address>,
+        len: usize,
+    ) -> Option<Vec<T>> {
+        Process::read_vec(self, address, len).ok()
+    }
+}
@@ -3,12 +3,12 @@
 // Mono backend.
 
 use asr::{
-    file_format::pe, future::retry, signature::Signature, string::ArrayCString, Address, Address32,
-    Address64, PointerSize, Process,
+    file_format::pe, signature::Signature, string::ArrayCString, Address, Address32, Address64,
+    PointerSize, Process,
 };
 use core::{
     array,
-    cell::RefCell,
+    cell::{Cell, RefCell},
     iter::{self, FusedIterator},
 };
 
@@ -16,6 +16,10 @@ use bytemuck::CheckedBitPattern;
 
 const CSTR: usize = 128;
 
+/// Number of consecutive failed reads of the static field of a [`UnityPointer`]
+/// after which the address of the static table is looked up again.
+const MAX_CONSECUTIVE_FAILURES: u32 = 600;
+
 /// Represents access to a Unity game that is using the standard Mono backend.
 pub struct Module {
     pointer_size: PointerSize,
@@ -112,6 +116,11 @@ impl Module {
         .fuse()
     }
 
+    /// Returns the [Mono version](Version) the module was attached with.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
     /// Looks for the specified binary [image](Image) inside the target process.
     /// An [image](Image) is a .NET DLL that is loaded
     /// by the game. The `Assembly-CSharp` [image](Image) is the main game
@@ -138,32 +147,6 @@ impl Module {
         self.get_image(process, "Assembly-CSharp")
     }
 
-    /// Attaches to a Unity game that is using the standard Mono backend. This
-    /// function automatically detects the [Mono version](Version). If you
-    /// know the version in advance or it fails detecting it, use
-    /// [`wait_attach`](Self::wait_attach) instead.
-    ///
-    /// This is the `await`able version of the
-    /// [`attach_auto_detect`](Self::attach_auto_detect) function, yielding back
-    /// to the runtime between each try.
-    pub async fn wait_attach_auto_detect(process: &Process) -> Module {
-        retry(|| Self::attach_auto_detect(process)).await
-    }
-
-    /// Looks for the `Assembly-CSharp` binary [image](Image) inside the target
-    /// process. An [image](Image) is a .NET DLL that
-    /// is loaded by the game. The `Assembly-CSharp` [image](Image) is the main
-    /// game assembly, and contains all the game logic. This function is a
-    /// shorthand for [`wait_get_image`](Self::wait_get_image) that accesses the
-    /// `Assembly-CSharp` [image](Image).
-    ///
-    /// This is the `await`able version of the
-    /// [`get_default_image`](Self::get_default_image) function, yielding back
-    /// to the runtime between each try.
-    pub async fn wait_get_default_image(&self, process: &Process) -> Image {
-        retry(|| self.get_default_image(process)).await
-    }
-
     #[inline]
     const fn size_of_ptr(&self) -> u64 {
         self.pointer_size as u64
@@ -271,18 +254,6 @@ impl Image {
                 .is_some_and(|name| name.matches(class_name))
         })
     }
-
-    /// Tries to find the specified [.NET class](struct@Class) in the image.
-    /// This is the `await`able version of the [`get_class`](Self::get_class)
-    /// function, yielding back to the runtime between each try.
-    pub async fn wait_get_class(
-        &self,
-        process: &Process,
-        module: &Module,
-        class_name: &str,
-    ) -> Class {
-        retry(|| self.get_class(process, module, class_name)).await
-    }
 }
 
 /// A .NET class that is part of an [`Image`](Image).
@@ -437,6 +408,32 @@ impl Class {
             .filter(|val| !val.is_null())
     }
 
+    /// Tries to find the class of the declared type of the field with the specified
+    /// name in the class. This only works for fields whose type is a class, as the
+    /// type of the other fields doesn't point to a class.
+    pub fn get_field_class(
+        &self,
+        process: &Process,
+        module: &Module,
+        field_name: &str,
+    ) -> Option<Class> {
+        let field = self.fields(process, module).find(|field| {
+            field
+                .get_name::<CSTR>(process, module)
+                .is_some_and(|name| name.matches(field_name))
+        })?;
+
+        // The `MonoType` of the field is the first member of `MonoClassField`, and its
+        // class is the first member of `MonoType`
+        process
+            .read_pointer(field.field, module.pointer_size)
+            .ok()
+            .filter(|val| !val.is_null())
+            .and_then(|field_type| process.read_pointer(field_type, module.pointer_size).ok())
+            .filter(|val| !val.is_null())
+            .map(|class| Class { class })
+    }
+
     /// Tries to find the parent class.
     pub fn get_parent(&self, process: &Process, module: &Module) -> Option<Class> {
         process
@@ -451,18 +448,15 @@ impl Class {
             .map(|class| Class { class })
     }
 
-    /// Tries to find a field with the specified name in the class. This returns
-    /// the offset of the field from the start of an instance of the class. If
-    /// it's a static field, the offset will be from the start of the static
-    /// table. This is the `await`able version of the
-    /// [`get_field_offset`](Self::get_field_offset) function.
-    pub async fn wait_get_field_offset(
-        &self,
-        process: &Process,
-        module: &Module,
-        name: &str,
-    ) -> u32 {
-        retry(|| self.get_field_offset(process, module, name)).await
+    /// Tries to find the size of an instance of the class, including the object header.
+    pub fn get_instance_size(&self, process: &Process, module: &Module) -> Option<u32> {
+        process
+            .read::<u32>(
+                self.class
+                    + module.offsets.monoclassdef_klass
+                    + module.offsets.monoclass_instance_size,
+            )
+            .ok()
     }
 }
 
@@ -497,6 +491,7 @@ impl Field {
 #[derive(Clone)]
 pub struct UnityPointer<const CAP: usize> {
     cache: RefCell<UnityPointerCache<CAP>>,
+    failures: Cell<u32>,
     class_name: &'static str,
     nr_of_parents: usize,
     fields: [&'static str; CAP],
@@ -509,6 +504,9 @@ struct UnityPointerCache<const CAP: usize> {
     offsets: [u64; CAP],
     resolved_offsets: usize,
     starting_class: Option<Class>,
+    /// Index and class of the last field that couldn't be found, so the fields of the
+    /// same class don't get scanned again on every attempt
+    missing_field: Option<(usize, Address)>,
 }
 
 impl<const CAP: usize> UnityPointer<CAP> {
@@ -529,10 +527,12 @@ impl<const CAP: usize> UnityPointer<CAP> {
             offsets: [u64::default(); CAP],
             resolved_offsets: usize::default(),
             starting_class: None,
+            missing_field: None,
         });
 
         Self {
             cache,
+            failures: Cell::new(0),
             class_name,
             nr_of_parents,
             fields: this_fields,
@@ -540,12 +540,52 @@ impl<const CAP: usize> UnityPointer<CAP> {
         }
     }
 
+    /// Looks up the starting class and every field of the path in the declared types of
+    /// the fields, without reading any object, so it works even if the objects don't
+    /// exist yet. Returns the name of the first class or field that can't be found.
+    ///
+    /// Fields specified as raw offsets, and the ones following them, aren't checked.
+    pub fn find_missing_field(
+        &self,
+        process: &Process,
+        module: &Module,
+        image: &Image,
+    ) -> Option<&'static str> {
+        let mut class = image.get_class(process, module, self.class_name);
+        for _ in 0..self.nr_of_parents {
+            class = class.and_then(|class| class.get_parent(process, module));
+        }
+
+        let Some(mut class) = class else {
+            return Some(self.class_name);
+        };
+
+        for (i, &field) in self.fields[..self.depth].iter().enumerate() {
+            if field.starts_with("0x") || field.parse::<u32>().is_ok() {
+                return None;
+            }
+
+            if class.get_field_offset(process, module, field).is_none() {
+                return Some(field);
+            }
+
+            if i + 1 < self.depth {
+                match class.get_field_class(process, module, field) {
+                    Some(field_class) => class = field_class,
+                    _ => return Some(field),
+                }
+            }
+        }
+
+        None
+    }
+
     /// Tries to resolve the pointer path for the `Mono` class specified
     fn find_offsets(&self, process: &Process, module: &Module, image: &Image) -> Option<()> {
         let mut cache = self.cache.borrow_mut();
 
         // If the pointer path has already been found, there's no need to continue
-        if cache.resolved_offsets == self.depth {
+        if cache.resolved_offsets == self.depth && !cache.base_address.is_null() {
             return Some(());
         }
 
@@ -575,6 +615,10 @@ impl<const CAP: usize> UnityPointer<CAP> {
             cache.base_address = starting_class.get_static_table(process, module)?;
         };
 
+        if cache.resolved_offsets == self.depth {
+            return Some(());
+        }
+
         // If we already resolved some offsets, we need to traverse them again starting from the base address
         // of the static table in order to recalculate the address of the farthest object we can reach.
         // If no offsets have been resolved yet, we just need to read the base address instead.
@@ -607,6 +651,10 @@ impl<const CAP: usize> UnityPointer<CAP> {
                             .map(|class| Class { class })?,
                     };
 
+                    if cache.missing_field == Some((i, current_class.class)) {
+                        return None;
+                    }
+
                     let val = current_class
                         .fields(process, module)
                         .find(|field| {
@@ -614,12 +662,15 @@ impl<const CAP: usize> UnityPointer<CAP> {
                                 .get_name::<CSTR>(process, module)
                                 .is_some_and(|name| name.matches(self.fields[i]))
                         })
-                        .and_then(|val| val.get_offset(process, module))?
-                        as u64;
-
-                    // Explicitly allowing this clippy because of borrowing rules shenanigans
-                    #[allow(clippy::let_and_return)]
-                    val
+                        .and_then(|val| val.get_offset(process, module));
+
+                    match val {
+                        Some(val) => val as u64,
+                        _ => {
+                            cache.missing_field = Some((i, current_class.class));
+                            return None;
+                        }
+                    }
                 }
             };
 
@@ -642,14 +693,47 @@ impl<const CAP: usize> UnityPointer<CAP> {
         image: &Image,
     ) -> Option<T> {
         self.find_offsets(process, module, image)?;
-        let cache = self.cache.borrow();
-        process
-            .read_pointer_path(
-                cache.base_address,
-                module.pointer_size,
-                &cache.offsets[..self.depth],
-            )
-            .ok()
+
+        let value = {
+            let cache = self.cache.borrow();
+            process
+                .read_pointer_path(
+                    cache.base_address,
+                    module.pointer_size,
+                    &cache.offsets[..self.depth],
+                )
+                .ok()
+        };
+
+        // The path gets walked again from the static table on every read, so a null object
+        // along it is expected (eg. an object only present in some scenes) and the offsets
+        // stay valid. Only if the static field itself keeps failing to be read, the static
+        // table is assumed to have moved and its address gets looked up again.
+        let static_field_readable = value.is_some() || {
+            let cache = self.cache.borrow();
+            process
+                .read_pointer(cache.base_address + cache.offsets[0], module.pointer_size)
+                .is_ok()
+        };
+
+        match static_field_readable {
+            true => self.failures.set(0),
+            false => {
+                let failures = self.failures.get().saturating_add(1);
+                self.failures.set(failures);
+
+                if failures == MAX_CONSECUTIVE_FAILURES {
+                    asr::print_limited::<128>(&format_args!(
+                        "  => Static table of {} unreadable {} times in a row: looking it up again",
+                        self.class_name, failures
+                    ));
+                    self.failures.set(0);
+                    self.cache.borrow_mut().base_address = Address::default();
+                }
+            }
+        }
+
+        value
     }
 }
 
@@ -668,6 +752,7 @@ struct Offsets {
     monoclass_runtime_info: u8,
     monoclass_vtable_size: u8,
     monoclass_parent: u8,
+    monoclass_instance_size: u8,
     monoclassfield_name: u8,
     monoclassfield_offset: u8,
     monoclassruntimeinfo_domain_vtables: u8,
@@ -694,6 +779,7 @@ impl Offsets {
                     monoclass_runtime_info: 0xF8,
                     monoclass_vtable_size: 0x18, // MonoVtable.data
                     monoclass_parent: 0x30,
+                    monoclass_instance_size: 0x1C,
                     monoclassfield_name: 0x8,
                     monoclassfield_offset: 0x18,
                     monoclassruntimeinfo_domain_vtables: 0x8,
@@ -715,6 +801,7 @@ impl Offsets {
                     monoclass_runtime_info: 0xD0,
                     monoclass_vtable_size: 0x5C,
                     monoclass_parent: 0x30,
+                    monoclass_instance_size: 0x1C,
                     monoclassfield_name: 0x8,
                     monoclassfield_offset: 0x18,
                     monoclassruntimeinfo_domain_vtables: 0x8,
@@ -736,6 +823,7 @@ impl Offsets {
                     monoclass_runtime_info: 0xD0,
                     monoclass_vtable_size: 0x5C,
                     monoclass_parent: 0x30,
+                    monoclass_instance_size: 0x1C,
                     monoclassfield_name: 0x8,
                     monoclassfield_offset: 0x18,
                     monoclassruntimeinfo_domain_vtables: 0x8,
@@ -759,6 +847,7 @@ impl Offsets {
                     monoclass_runtime_info: 0xA4,
                     monoclass_vtable_size: 0xC, // MonoVtable.data
                     monoclass_parent: 0x24,
+                    monoclass_instance_size: 0x10,
                     monoclassfield_name: 0x4,
                     monoclassfield_offset: 0xC,
                     monoclassruntimeinfo_domain_vtables: 0x4,
@@ -780,6 +869,7 @@ impl Offsets {
                     monoclass_runtime_info: 0x84,
                     monoclass_vtable_size: 0x38,
                     monoclass_parent: 0x20,
+                    monoclass_instance_size: 0x10,
                     monoclassfield_name: 0x4,
                     monoclassfield_offset: 0xC,
                     monoclassruntimeinfo_domain_vtables: 0x4,
@@ -801,6 +891,7 @@ impl Offsets {
                     monoclass_runtime_info: 0x7C,
                     monoclass_vtable_size: 0x38,
                     monoclass_parent: 0x20,
+                    monoclass_instance_size: 0x10,
                     monoclassfield_name: 0x4,
                     monoclassfield_offset: 0xC,
                     monoclassruntimeinfo_domain_vtables: 0x4,
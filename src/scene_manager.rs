@@ -1,7 +1,20 @@
 use asr::{
-    file_format::pe, future::retry, signature::Signature, string::ArrayCString, Address, Address32,
-    PointerSize, Process,
+    file_format::pe, future::next_tick, signature::Signature, string::ArrayCString, Address,
+    Address32, PointerSize, Process,
 };
+use core::cell::Cell;
+
+/// Number of failed attempts after which [`SceneManager::wait_attach`] reports
+/// that the scene manager could not be found.
+const ATTACH_ATTEMPTS_BEFORE_WARNING: u32 = 600;
+
+/// Number of consecutive failed reads of the active scene after which
+/// [`SceneManager::refresh`] attaches to the scene manager again.
+const FAILED_READS_BEFORE_REATTACH: u32 = 300;
+
+/// Names the Unity player module can show up with, tried in order. Some launchers
+/// and compatibility layers (eg. Wine/Proton) report module names in lowercase.
+const UNITY_PLAYER_MODULES: &[&str] = &["UnityPlayer.dll", "unityplayer.dll"];
 
 /// The scene manager allows you to easily identify the current scene loaded in
 /// the attached Unity game.
@@ -12,6 +25,10 @@ pub struct SceneManager {
     pointer_size: PointerSize,
     address: Address,
     offsets: &'static Offsets,
+    /// Name of the Unity player module the scene manager was found in
+    module_name: &'static str,
+    /// Number of consecutive failed reads of the active scene
+    failed_reads: Cell<u32>,
 }
 
 impl SceneManager {
@@ -22,17 +39,7 @@ impl SceneManager {
         const SIG_32_2: Signature<6> = Signature::new("53 8D 41 ?? 33 DB");
         const SIG_32_3: Signature<14> = Signature::new("55 8B EC 83 EC 18 A1 ???????? 33 C9 53");
 
-        let unity_player = process
-            .get_module_address("UnityPlayer.dll")
-            .ok()
-            .and_then(|address| {
-                Some((address, pe::read_size_of_image(process, address)? as u64))
-            })?;
-
-        let pointer_size = match pe::MachineType::read(process, unity_player.0)? {
-            pe::MachineType::X86_64 => PointerSize::Bit64,
-            _ => PointerSize::Bit32,
-        };
+        let (unity_player, pointer_size, module_name) = Self::unity_player(process)?;
 
         // There are multiple signatures that can be used, depending on the version of Unity
         // used in the target game.
@@ -62,25 +69,110 @@ impl SceneManager {
             pointer_size,
             address,
             offsets,
+            module_name,
+            failed_reads: Cell::new(0),
         })
     }
 
+    /// Looks for the Unity player module, returning its address range along
+    /// with the pointer size of the game and the name the module was found with.
+    fn unity_player(process: &Process) -> Option<((Address, u64), PointerSize, &'static str)> {
+        let (unity_player, module_name) = UNITY_PLAYER_MODULES.iter().find_map(|&name| {
+            let address = process.get_module_address(name).ok()?;
+            Some((
+                (address, pe::read_size_of_image(process, address)? as u64),
+                name,
+            ))
+        })?;
+
+        let pointer_size = match pe::MachineType::read(process, unity_player.0)? {
+            pe::MachineType::X86_64 => PointerSize::Bit64,
+            _ => PointerSize::Bit32,
+        };
+
+        Some((unity_player, pointer_size, module_name))
+    }
+
     /// Attaches to the scene manager in the given process.
     ///
     /// This is the `await`able version of the [`attach`](Self::attach)
     /// function, yielding back to the runtime between each try.
+    ///
+    /// If the scene manager can't be found after a while, a message is logged
+    /// to help identifying unsupported Unity versions. It keeps retrying anyway,
+    /// as the module might just be loading late.
     pub async fn wait_attach(process: &Process) -> SceneManager {
-        retry(|| Self::attach(process)).await
+        let mut attempts: u32 = 0;
+
+        loop {
+            if let Some(scene_manager) = Self::attach(process) {
+                return scene_manager;
+            }
+
+            attempts = attempts.saturating_add(1);
+            if attempts == ATTACH_ATTEMPTS_BEFORE_WARNING {
+                match Self::unity_player(process) {
+                    Some((_, pointer_size, _)) => asr::print_limited::<128>(&format_args!(
+                        "    => No Scene Manager signature matched ({}-bit game). Still retrying...",
+                        pointer_size as u8 * 8
+                    )),
+                    _ => asr::print_message(
+                        "    => Unity player module not found yet. Still retrying...",
+                    ),
+                }
+            }
+
+            next_tick().await;
+        }
+    }
+
+    /// Returns the pointer size of the attached game.
+    pub fn pointer_size(&self) -> PointerSize {
+        self.pointer_size
+    }
+
+    /// Returns the name the Unity player module was found with.
+    pub fn module_name(&self) -> &'static str {
+        self.module_name
+    }
+
+    /// Attaches to the scene manager again if the active scene couldn't be read
+    /// for a while.
+    ///
+    /// The scene manager is dereferenced once when attaching, as the pointer is not
+    /// expected to change while the game is open. This is a safety net in case it
+    /// does anyway, eg. after a major reload. Returns whether the scene manager got
+    /// re-attached at a different address.
+    pub fn refresh(&mut self, process: &Process) -> bool {
+        if self.failed_reads.get() < FAILED_READS_BEFORE_REATTACH {
+            return false;
+        }
+
+        self.failed_reads.set(0);
+
+        match Self::attach(process) {
+            Some(scene_manager) => {
+                let changed = scene_manager.address != self.address;
+                *self = scene_manager;
+                changed
+            }
+            _ => false,
+        }
     }
 
     /// Tries to retrieve the current active scene.
     fn get_current_scene(&self, process: &Process) -> Option<Scene> {
-        Some(Scene {
-            address: process
-                .read_pointer(self.address + self.offsets.active_scene, self.pointer_size)
-                .ok()
-                .filter(|val| !val.is_null())?,
-        })
+        let scene = process
+            .read_pointer(self.address + self.offsets.active_scene, self.pointer_size)
+            .ok()
+            .filter(|val| !val.is_null());
+
+        self.failed_reads.set(match scene {
+            Some(_) => 0,
+            None => self.failed_reads.get().saturating_add(1),
+        });
+
+        Some(Scene { address: scene? })
     }
 
     /// Returns the full path to the current scene. Use [`get_scene_name`]
@@ -120,17 +212,61 @@ pub struct Scene {
 
 impl Scene {
     /// Returns the full path to the scene.
+    ///
+    /// If the path is longer than the buffer, `None` is returned instead of
+    /// the truncated path.
     pub fn path<const N: usize>(
         &self,
         process: &Process,
         scene_manager: &SceneManager,
     ) -> Option<ArrayCString<N>> {
         process
-            .read_pointer_path(
+            .read_pointer_path::<ArrayCString<N>>(
                 self.address,
                 scene_manager.pointer_size,
                 &[scene_manager.offsets.asset_path as u64, 0x0],
             )
             .ok()
+            .and_then(untruncated)
+    }
+}
+
+/// Returns the path read into the buffer, or `None` if it fills the whole buffer,
+/// as it might have been truncated
+fn untruncated<const N: usize>(path: ArrayCString<N>) -> Option<ArrayCString<N>> {
+    Some(path).filter(|path| path.as_bytes().len() < N)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asr::game_engine::unity::get_scene_name;
+
+    /// Returns the buffer the specified path gets read into
+    fn read_path<const N: usize>(path: &[u8]) -> ArrayCString<N> {
+        let mut bytes = [0; N];
+        let len = path.len().min(N);
+        bytes[..len].copy_from_slice(&path[..len]);
+        bytemuck::cast(bytes)
+    }
+
+    #[test]
+    fn long_paths_keep_their_scene_name() {
+        let path = [b"Assets/".as_slice(), &[b'a'; 200], b"/Level_X.unity"].concat();
+        assert!(path.len() > 128);
+
+        let scene = untruncated(read_path::<256>(&path)).unwrap();
+        assert_eq!(get_scene_name(&scene), b"Level_X");
+    }
+
+    #[test]
+    fn truncated_paths_are_discarded() {
+        let path = [b"Assets/".as_slice(), &[b'a'; 200], b"/Level_X.unity"].concat();
+
+        // The buffer is filled up to the last byte, so the name might be cut short
+        assert!(untruncated(read_path::<128>(&path)).is_none());
+        let exact = &path[path.len() - 128..];
+        assert!(untruncated(read_path::<128>(exact)).is_none());
+        assert!(untruncated(read_path::<128>(&path[path.len() - 127..])).is_some());
     }
 }
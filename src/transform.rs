@@ -0,0 +1,162 @@
+use asr::{Address, Address64, PointerSize, Process};
+
+/// Maximum number of parents walked through when computing the world position,
+/// in order to avoid looping forever on corrupted data.
+const MAX_HIERARCHY_DEPTH: usize = 64;
+
+/// A native Unity `Transform`, allowing to retrieve the world position of the
+/// game object it's attached to.
+///
+/// This is useful when a class exposes its position only through its `Transform`
+/// component, with no managed field holding it. Only 64-bit games are supported.
+pub struct Transform {
+    address: Address,
+}
+
+impl Transform {
+    /// Retrieves the native `Transform` of the game object the specified managed
+    /// `Component` (eg. a `MonoBehaviour`) is attached to.
+    pub fn from_component(
+        process: &Process,
+        pointer_size: PointerSize,
+        component: Address64,
+    ) -> Option<Self> {
+        if pointer_size != PointerSize::Bit64 || component.is_null() {
+            return None;
+        }
+
+        // m_CachedPtr: the native counterpart of the managed component
+        let native_component = process
+            .read::<Address64>(component + OFFSETS.cached_ptr)
+            .ok()
+            .filter(|val| !val.is_null())?;
+
+        // The game object holds a list of components, the first of which is always its Transform
+        let game_object = process
+            .read::<Address64>(native_component + OFFSETS.game_object)
+            .ok()
+            .filter(|val| !val.is_null())?;
+
+        let address = process
+            .read::<Address64>(game_object + OFFSETS.components)
+            .ok()
+            .filter(|val| !val.is_null())
+            .and_then(|components| process.read::<Address64>(components + 0x8).ok())
+            .filter(|val| !val.is_null())?;
+
+        Some(Self {
+            address: address.into(),
+        })
+    }
+
+    /// Computes the world position of the transform.
+    ///
+    /// Unity stores the local position, rotation and scale of each transform in
+    /// its hierarchy, so the world position is obtained by applying the transform
+    /// of each parent in turn.
+    pub fn position(&self, process: &Process) -> Option<[f32; 3]> {
+        let hierarchy = process
+            .read::<Address64>(self.address + OFFSETS.hierarchy)
+            .ok()
+            .filter(|val| !val.is_null())?;
+        let mut index = process.read::<i32>(self.address + OFFSETS.index).ok()?;
+
+        let [local_transforms, parent_indices] = process
+            .read::<[Address64; 2]>(hierarchy + OFFSETS.local_transforms)
+            .ok()?;
+
+        if local_transforms.is_null() || parent_indices.is_null() || index < 0 {
+            return None;
+        }
+
+        let read_trs = |index: i32| {
+            process
+                .read::<TransformData>(
+                    local_transforms + index as u64 * size_of::<TransformData>() as u64,
+                )
+                .ok()
+        };
+
+        let mut position = read_trs(index)?.translation();
+
+        for _ in 0..MAX_HIERARCHY_DEPTH {
+            index = process
+                .read::<i32>(parent_indices + index as u64 * size_of::<i32>() as u64)
+                .ok()?;
+
+            if index < 0 {
+                return position
+                    .iter()
+                    .all(|val| val.is_finite())
+                    .then_some(position);
+            }
+
+            position = read_trs(index)?.apply(position);
+        }
+
+        None
+    }
+}
+
+struct Offsets {
+    cached_ptr: u64,
+    game_object: u64,
+    components: u64,
+    hierarchy: u64,
+    index: u64,
+    local_transforms: u64,
+}
+
+/// Offsets used by 64-bit builds of Unity 2019 and later
+const OFFSETS: Offsets = Offsets {
+    cached_ptr: 0x10,
+    game_object: 0x30,
+    components: 0x30,
+    hierarchy: 0x38,
+    index: 0x40,
+    local_transforms: 0x18,
+};
+
+/// Local translation, rotation (as a quaternion) and scale of a transform, as
+/// stored in the transform hierarchy. Each field is padded to 4 floats.
+#[derive(Copy, Clone, bytemuck::Zeroable, bytemuck::Pod)]
+#[repr(C)]
+struct TransformData {
+    translation: [f32; 4],
+    rotation: [f32; 4],
+    scale: [f32; 4],
+}
+
+impl TransformData {
+    fn translation(&self) -> [f32; 3] {
+        let [x, y, z, _] = self.translation;
+        [x, y, z]
+    }
+
+    /// Transforms a position from the local space of this transform to the space of its parent
+    fn apply(&self, position: [f32; 3]) -> [f32; 3] {
+        let [sx, sy, sz, _] = self.scale;
+        let v = [position[0] * sx, position[1] * sy, position[2] * sz];
+
+        // Rotation of a vector by a quaternion: v' = v + w * t + q × t, with t = 2 * (q × v)
+        let [qx, qy, qz, qw] = self.rotation;
+        let q = [qx, qy, qz];
+        let t = cross(q, v).map(|val| val * 2.0);
+        let qt = cross(q, t);
+
+        let [tx, ty, tz, _] = self.translation;
+        [
+            v[0] + qw * t[0] + qt[0] + tx,
+            v[1] + qw * t[1] + qt[1] + ty,
+            v[2] + qw * t[2] + qt[2] + tz,
+        ]
+    }
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}